@@ -0,0 +1,122 @@
+game-title = BEVY BREAKOUT
+loading = Wird geladen…
+loading-failed = { $path } konnte nicht geladen werden
+loading-continue = Beliebige Taste drücken, um fortzufahren
+
+## Menus
+
+main-menu = HAUPTMENÜ
+menu-continue = Fortsetzen
+menu-play = Spielen
+menu-coop = Koop
+menu-versus = Duell
+menu-time-attack = Zeitrennen
+menu-endless = Endlos
+menu-online = Online
+menu-editor = Editor
+menu-settings = Optionen
+menu-achievements = Erfolge
+menu-statistics = Statistik
+menu-back = Zurück
+
+## Settings
+
+settings-title = OPTIONEN
+settings-restart-note = Layoutänderungen gelten nach dem Neustart
+setting-rows = Reihen
+setting-columns = Spalten
+setting-countdown = Countdown
+setting-time-limit = Zeitlimit
+setting-minutes = { $minutes } Min.
+setting-theme = Design
+setting-language = Sprache
+setting-decrease = -
+setting-increase = +
+theme-light = Hell
+theme-dark = Dunkel
+theme-retro = Retro
+theme-neon = Neon
+setting-palette = Palette
+setting-brick-symbols = Symbole
+setting-on = An
+setting-off = Aus
+palette-standard = Standard
+palette-deuteranopia = Deuteranopie
+palette-protanopia = Protanopie
+palette-tritanopia = Tritanopie
+palette-high-contrast = Hoher Kontrast
+
+## Game
+
+ready-prompt = Drücke Leertaste, wenn du bereit bist
+paused = PAUSE
+pause-resume = Weiter
+pause-restart = Level neu starten
+pause-quit = Zum Menü
+outcome-lost = SPIEL VORBEI
+outcome-cleared = LEVEL GESCHAFFT
+outcome-winner = SPIELER { $player } GEWINNT
+return-prompt = Drücke Leertaste, um zum Menü zurückzukehren
+hud-solo = Leben: { $lives }   Punkte: { $score }
+hud-coop = Leben: { $lives }   S1: { $first }   S2: { $second }
+hud-versus = S1 Leben: { $first-lives }  Punkte: { $first-score }      S2 Leben: { $second-lives }  Punkte: { $second-score }
+hud-time-attack = Leben: { $lives }   Stufe { $stage }/{ $stages }   Zeit: { $time }   Zwischenzeiten: { $splits }
+result-time = Zeit: { $time }
+result-split = Stufe { $stage }: { $time }
+result-split-versus = Stufe { $stage }: { $time } ({ $difference })
+result-best = Persönliche Bestzeit: { $time } ({ $difference })
+result-new-best = Neue persönliche Bestzeit!
+result-rank = Platz { $rank } von { $count }
+result-unranked = Nicht unter den besten { $count }
+hud-endless = Leben: { $lives }   Reihen: { $rows }   Nächste Reihe in { $seconds } s oder { $hits } Treffern
+result-rows = Überstandene Reihen: { $rows }
+result-best-rows = Rekord: { $rows } Reihen
+
+## Achievements
+
+achievements-title = ERFOLGE
+achievement-unlocked = Erfolg freigeschaltet: { $name }
+achievement-flawless = Makellos
+achievement-flawless-description = Schließe ein Level ab, ohne ein Leben zu verlieren
+achievement-combo = Kettenreaktion
+achievement-combo-description = Zerstöre 10 Steine, ohne dass der Ball einen Schläger berührt
+achievement-top-speed = Höchstgeschwindigkeit
+achievement-top-speed-description = Bringe den Ball auf seine Höchstgeschwindigkeit
+achievement-first-try = Auf Anhieb
+achievement-first-try-description = Schließe ein Level beim ersten Versuch ab
+
+## Statistics
+
+statistics-title = STATISTIK
+stat-games-played = Gespielte Partien
+stat-play-time = Spielzeit
+stat-bricks-broken = Zerstörte Steine
+stat-balls-lost = Verlorene Bälle
+stat-paddle-hits = Schlägertreffer
+stat-longest-rally = Längster Ballwechsel
+stat-fastest-clear = Schnellster Abschluss
+stat-endless-rows = Meiste Reihen (Endlos)
+
+## Editor
+
+brick-normal = Normaler
+brick-tough = Harter
+brick-unbreakable = Unzerstörbarer
+editor-brush = Pinsel: { $kind } Stein, Farbe { $color }
+editor-help =
+    Linksklick malen  Rechtsklick löschen  Umschalt+Klick umfärben  1-0 Farbe  N/T/U Typ  M Reihenbewegung
+    Enter testen  Strg+S speichern  Strg+O nächstes öffnen  F2 umbenennen  Strg+N neu  Esc Menü
+editor-name = Level: { $name }
+editor-naming = Levelname: { $name }_
+editor-no-levels = Keine gespeicherten Levels vorhanden
+editor-list-failed = Levels konnten nicht aufgelistet werden: { $error }
+editor-saved = { $path } gespeichert
+editor-save-failed = Speichern von { $path } fehlgeschlagen: { $error }
+editor-loaded = { $path } geladen
+editor-load-failed = Laden von { $path } fehlgeschlagen: { $error }
+editor-new = Neues Level mit { $columns } Spalten
+editor-motion = Reihe { $row }: { $motion }
+motion-none = Fest
+motion-sway = Pendeln
+motion-loop = Kreisen
+motion-spin = Drehen
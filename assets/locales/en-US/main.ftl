@@ -0,0 +1,122 @@
+game-title = BEVY BREAKOUT
+loading = Loading…
+loading-failed = Could not load { $path }
+loading-continue = Press any key to continue
+
+## Menus
+
+main-menu = MAIN MENU
+menu-continue = Continue
+menu-play = Play
+menu-coop = Co-op
+menu-versus = Versus
+menu-time-attack = Time attack
+menu-endless = Endless
+menu-online = Online
+menu-editor = Editor
+menu-settings = Settings
+menu-achievements = Achievements
+menu-statistics = Statistics
+menu-back = Back
+
+## Settings
+
+settings-title = SETTINGS
+settings-restart-note = Layout changes apply on restart
+setting-rows = Rows
+setting-columns = Columns
+setting-countdown = Countdown
+setting-time-limit = Time limit
+setting-minutes = { $minutes } min
+setting-theme = Theme
+setting-language = Language
+setting-decrease = -
+setting-increase = +
+theme-light = Light
+theme-dark = Dark
+theme-retro = Retro
+theme-neon = Neon
+setting-palette = Palette
+setting-brick-symbols = Brick symbols
+setting-on = On
+setting-off = Off
+palette-standard = Standard
+palette-deuteranopia = Deuteranopia
+palette-protanopia = Protanopia
+palette-tritanopia = Tritanopia
+palette-high-contrast = High contrast
+
+## Game
+
+ready-prompt = Press space when you are ready
+paused = PAUSED
+pause-resume = Resume
+pause-restart = Restart level
+pause-quit = Quit to menu
+outcome-lost = GAME OVER
+outcome-cleared = LEVEL CLEAR
+outcome-winner = PLAYER { $player } WINS
+return-prompt = Press space to return to the menu
+hud-solo = Lives: { $lives }   Score: { $score }
+hud-coop = Lives: { $lives }   P1: { $first }   P2: { $second }
+hud-versus = P1 Lives: { $first-lives }  Score: { $first-score }      P2 Lives: { $second-lives }  Score: { $second-score }
+hud-time-attack = Lives: { $lives }   Stage { $stage }/{ $stages }   Time: { $time }   Splits: { $splits }
+result-time = Time: { $time }
+result-split = Stage { $stage }: { $time }
+result-split-versus = Stage { $stage }: { $time } ({ $difference })
+result-best = Personal best: { $time } ({ $difference })
+result-new-best = New personal best!
+result-rank = Rank { $rank } of { $count }
+result-unranked = Not in the top { $count }
+hud-endless = Lives: { $lives }   Rows: { $rows }   Next row in { $seconds }s or { $hits } hits
+result-rows = Rows survived: { $rows }
+result-best-rows = Best: { $rows } rows
+
+## Achievements
+
+achievements-title = ACHIEVEMENTS
+achievement-unlocked = Achievement unlocked: { $name }
+achievement-flawless = Flawless
+achievement-flawless-description = Clear a level without losing a life
+achievement-combo = Chain Reaction
+achievement-combo-description = Break 10 bricks without the ball touching a paddle
+achievement-top-speed = Top Speed
+achievement-top-speed-description = Push the ball to its maximum speed
+achievement-first-try = First Try
+achievement-first-try-description = Clear a level on your first attempt
+
+## Statistics
+
+statistics-title = STATISTICS
+stat-games-played = Games played
+stat-play-time = Play time
+stat-bricks-broken = Bricks broken
+stat-balls-lost = Balls lost
+stat-paddle-hits = Paddle hits
+stat-longest-rally = Longest rally
+stat-fastest-clear = Fastest clear
+stat-endless-rows = Most endless rows
+
+## Editor
+
+brick-normal = Normal
+brick-tough = Tough
+brick-unbreakable = Unbreakable
+editor-brush = Brush: { $kind } brick, colour { $color }
+editor-help =
+    LMB paint  RMB erase  Shift+LMB recolour  1-0 colour  N/T/U type  M row motion
+    Enter test-play  Ctrl+S save  Ctrl+O open next  F2 rename  Ctrl+N new  Esc menu
+editor-name = Level: { $name }
+editor-naming = Level name: { $name }_
+editor-no-levels = No saved levels to open
+editor-list-failed = Failed to list levels: { $error }
+editor-saved = Saved { $path }
+editor-save-failed = Failed to save { $path }: { $error }
+editor-loaded = Loaded { $path }
+editor-load-failed = Failed to load { $path }: { $error }
+editor-new = New level with { $columns } columns
+editor-motion = Row { $row }: { $motion }
+motion-none = Static
+motion-sway = Sway
+motion-loop = Loop
+motion-spin = Spin
@@ -0,0 +1,122 @@
+game-title = BEVY BREAKOUT
+loading = Cargando…
+loading-failed = No se pudo cargar { $path }
+loading-continue = Pulsa cualquier tecla para continuar
+
+## Menus
+
+main-menu = MENÚ PRINCIPAL
+menu-continue = Continuar
+menu-play = Jugar
+menu-coop = Cooperativo
+menu-versus = Versus
+menu-time-attack = Contrarreloj
+menu-endless = Sin fin
+menu-online = En línea
+menu-editor = Editor
+menu-settings = Opciones
+menu-achievements = Logros
+menu-statistics = Estadísticas
+menu-back = Volver
+
+## Settings
+
+settings-title = OPCIONES
+settings-restart-note = Los cambios de diseño se aplican al reiniciar
+setting-rows = Filas
+setting-columns = Columnas
+setting-countdown = Cuenta atrás
+setting-time-limit = Límite de tiempo
+setting-minutes = { $minutes } min
+setting-theme = Tema
+setting-language = Idioma
+setting-decrease = -
+setting-increase = +
+theme-light = Claro
+theme-dark = Oscuro
+theme-retro = Retro
+theme-neon = Neón
+setting-palette = Paleta
+setting-brick-symbols = Símbolos
+setting-on = Sí
+setting-off = No
+palette-standard = Estándar
+palette-deuteranopia = Deuteranopía
+palette-protanopia = Protanopía
+palette-tritanopia = Tritanopía
+palette-high-contrast = Alto contraste
+
+## Game
+
+ready-prompt = Pulsa espacio cuando estés listo
+paused = PAUSA
+pause-resume = Continuar
+pause-restart = Reiniciar nivel
+pause-quit = Salir al menú
+outcome-lost = FIN DE LA PARTIDA
+outcome-cleared = NIVEL SUPERADO
+outcome-winner = GANA EL JUGADOR { $player }
+return-prompt = Pulsa espacio para volver al menú
+hud-solo = Vidas: { $lives }   Puntos: { $score }
+hud-coop = Vidas: { $lives }   J1: { $first }   J2: { $second }
+hud-versus = J1 Vidas: { $first-lives }  Puntos: { $first-score }      J2 Vidas: { $second-lives }  Puntos: { $second-score }
+hud-time-attack = Vidas: { $lives }   Fase { $stage }/{ $stages }   Tiempo: { $time }   Parciales: { $splits }
+result-time = Tiempo: { $time }
+result-split = Fase { $stage }: { $time }
+result-split-versus = Fase { $stage }: { $time } ({ $difference })
+result-best = Mejor marca: { $time } ({ $difference })
+result-new-best = ¡Nueva mejor marca!
+result-rank = Puesto { $rank } de { $count }
+result-unranked = Fuera de los { $count } mejores
+hud-endless = Vidas: { $lives }   Filas: { $rows }   Siguiente fila en { $seconds } s o { $hits } golpes
+result-rows = Filas superadas: { $rows }
+result-best-rows = Récord: { $rows } filas
+
+## Achievements
+
+achievements-title = LOGROS
+achievement-unlocked = Logro desbloqueado: { $name }
+achievement-flawless = Impecable
+achievement-flawless-description = Completa un nivel sin perder ninguna vida
+achievement-combo = Reacción en cadena
+achievement-combo-description = Rompe 10 ladrillos sin que la bola toque una pala
+achievement-top-speed = Velocidad máxima
+achievement-top-speed-description = Lleva la bola a su velocidad máxima
+achievement-first-try = A la primera
+achievement-first-try-description = Completa un nivel en el primer intento
+
+## Statistics
+
+statistics-title = ESTADÍSTICAS
+stat-games-played = Partidas jugadas
+stat-play-time = Tiempo de juego
+stat-bricks-broken = Ladrillos rotos
+stat-balls-lost = Bolas perdidas
+stat-paddle-hits = Golpes de pala
+stat-longest-rally = Peloteo más largo
+stat-fastest-clear = Nivel más rápido
+stat-endless-rows = Más filas (sin fin)
+
+## Editor
+
+brick-normal = normal
+brick-tough = resistente
+brick-unbreakable = irrompible
+editor-brush = Pincel: ladrillo { $kind }, color { $color }
+editor-help =
+    Clic izq. pintar  Clic der. borrar  Mayús+clic recolorear  1-0 color  N/T/U tipo  M movimiento de fila
+    Intro probar  Ctrl+S guardar  Ctrl+O abrir siguiente  F2 renombrar  Ctrl+N nuevo  Esc menú
+editor-name = Nivel: { $name }
+editor-naming = Nombre del nivel: { $name }_
+editor-no-levels = No hay niveles guardados
+editor-list-failed = Error al listar los niveles: { $error }
+editor-saved = { $path } guardado
+editor-save-failed = Error al guardar { $path }: { $error }
+editor-loaded = { $path } cargado
+editor-load-failed = Error al cargar { $path }: { $error }
+editor-new = Nuevo nivel con { $columns } columnas
+editor-motion = Fila { $row }: { $motion }
+motion-none = Fija
+motion-sway = Vaivén
+motion-loop = Bucle
+motion-spin = Giro
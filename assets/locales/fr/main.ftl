@@ -0,0 +1,122 @@
+game-title = BEVY BREAKOUT
+loading = Chargement…
+loading-failed = Impossible de charger { $path }
+loading-continue = Appuyez sur une touche pour continuer
+
+## Menus
+
+main-menu = MENU PRINCIPAL
+menu-continue = Continuer
+menu-play = Jouer
+menu-coop = Coop
+menu-versus = Duel
+menu-time-attack = Contre-la-montre
+menu-endless = Sans fin
+menu-online = En ligne
+menu-editor = Éditeur
+menu-settings = Options
+menu-achievements = Succès
+menu-statistics = Statistiques
+menu-back = Retour
+
+## Settings
+
+settings-title = OPTIONS
+settings-restart-note = La disposition change au redémarrage
+setting-rows = Rangées
+setting-columns = Colonnes
+setting-countdown = Décompte
+setting-time-limit = Limite de temps
+setting-minutes = { $minutes } min
+setting-theme = Thème
+setting-language = Langue
+setting-decrease = -
+setting-increase = +
+theme-light = Clair
+theme-dark = Sombre
+theme-retro = Rétro
+theme-neon = Néon
+setting-palette = Palette
+setting-brick-symbols = Symboles
+setting-on = Oui
+setting-off = Non
+palette-standard = Standard
+palette-deuteranopia = Deutéranopie
+palette-protanopia = Protanopie
+palette-tritanopia = Tritanopie
+palette-high-contrast = Contraste élevé
+
+## Game
+
+ready-prompt = Appuyez sur espace quand vous êtes prêt
+paused = PAUSE
+pause-resume = Reprendre
+pause-restart = Recommencer
+pause-quit = Quitter vers le menu
+outcome-lost = PARTIE TERMINÉE
+outcome-cleared = NIVEAU TERMINÉ
+outcome-winner = VICTOIRE DU JOUEUR { $player }
+return-prompt = Appuyez sur espace pour revenir au menu
+hud-solo = Vies : { $lives }   Score : { $score }
+hud-coop = Vies : { $lives }   J1 : { $first }   J2 : { $second }
+hud-versus = J1 Vies : { $first-lives }  Score : { $first-score }      J2 Vies : { $second-lives }  Score : { $second-score }
+hud-time-attack = Vies : { $lives }   Niveau { $stage }/{ $stages }   Temps : { $time }   Intermédiaires : { $splits }
+result-time = Temps : { $time }
+result-split = Niveau { $stage } : { $time }
+result-split-versus = Niveau { $stage } : { $time } ({ $difference })
+result-best = Record personnel : { $time } ({ $difference })
+result-new-best = Nouveau record personnel !
+result-rank = Rang { $rank } sur { $count }
+result-unranked = Hors du top { $count }
+hud-endless = Vies : { $lives }   Rangées : { $rows }   Prochaine rangée dans { $seconds } s ou { $hits } coups
+result-rows = Rangées survécues : { $rows }
+result-best-rows = Record : { $rows } rangées
+
+## Achievements
+
+achievements-title = SUCCÈS
+achievement-unlocked = Succès débloqué : { $name }
+achievement-flawless = Sans faute
+achievement-flawless-description = Terminez un niveau sans perdre de vie
+achievement-combo = Réaction en chaîne
+achievement-combo-description = Cassez 10 briques sans que la balle touche une raquette
+achievement-top-speed = Pleine vitesse
+achievement-top-speed-description = Poussez la balle à sa vitesse maximale
+achievement-first-try = Du premier coup
+achievement-first-try-description = Terminez un niveau dès la première tentative
+
+## Statistics
+
+statistics-title = STATISTIQUES
+stat-games-played = Parties jouées
+stat-play-time = Temps de jeu
+stat-bricks-broken = Briques cassées
+stat-balls-lost = Balles perdues
+stat-paddle-hits = Renvois de raquette
+stat-longest-rally = Plus long échange
+stat-fastest-clear = Niveau le plus rapide
+stat-endless-rows = Record de rangées (sans fin)
+
+## Editor
+
+brick-normal = normale
+brick-tough = solide
+brick-unbreakable = incassable
+editor-brush = Pinceau : brique { $kind }, couleur { $color }
+editor-help =
+    Clic gauche peindre  Clic droit effacer  Maj+clic recolorer  1-0 couleur  N/T/U type  M mouvement de rangée
+    Entrée tester  Ctrl+S enregistrer  Ctrl+O ouvrir le suivant  F2 renommer  Ctrl+N nouveau  Échap menu
+editor-name = Niveau : { $name }
+editor-naming = Nom du niveau : { $name }_
+editor-no-levels = Aucun niveau enregistré
+editor-list-failed = Impossible de lister les niveaux : { $error }
+editor-saved = { $path } enregistré
+editor-save-failed = Échec de l'enregistrement de { $path } : { $error }
+editor-loaded = { $path } chargé
+editor-load-failed = Échec du chargement de { $path } : { $error }
+editor-new = Nouveau niveau de { $columns } colonnes
+editor-motion = Rangée { $row } : { $motion }
+motion-none = Immobile
+motion-sway = Balancement
+motion-loop = Boucle
+motion-spin = Rotation
@@ -0,0 +1,212 @@
+use super::game::HeadlessRun;
+use super::locale::Locale;
+use super::save_dir;
+use super::theme::{Theme, ThemeColor};
+use bevy::prelude::*;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const TOAST_SECONDS: f32 = 3.0;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub(crate) enum Achievement {
+    Flawless,
+    Combo,
+    TopSpeed,
+    FirstTry,
+}
+
+impl Achievement {
+    pub(crate) const ALL: [Achievement; 4] = [
+        Achievement::Flawless,
+        Achievement::Combo,
+        Achievement::TopSpeed,
+        Achievement::FirstTry,
+    ];
+
+    fn id(self) -> &'static str {
+        match self {
+            Achievement::Flawless => "flawless",
+            Achievement::Combo => "combo",
+            Achievement::TopSpeed => "top-speed",
+            Achievement::FirstTry => "first-try",
+        }
+    }
+
+    pub(crate) fn name_id(self) -> &'static str {
+        match self {
+            Achievement::Flawless => "achievement-flawless",
+            Achievement::Combo => "achievement-combo",
+            Achievement::TopSpeed => "achievement-top-speed",
+            Achievement::FirstTry => "achievement-first-try",
+        }
+    }
+
+    pub(crate) fn description_id(self) -> &'static str {
+        match self {
+            Achievement::Flawless => "achievement-flawless-description",
+            Achievement::Combo => "achievement-combo-description",
+            Achievement::TopSpeed => "achievement-top-speed-description",
+            Achievement::FirstTry => "achievement-first-try-description",
+        }
+    }
+}
+
+#[derive(Event)]
+pub(crate) struct Unlock(pub Achievement);
+
+// Unlocks are stored one id per line.
+#[derive(Resource, Default)]
+pub(crate) struct Achievements {
+    unlocked: Vec<Achievement>,
+}
+
+impl Achievements {
+    fn path() -> PathBuf {
+        save_dir().join("achievements.txt")
+    }
+
+    fn load() -> io::Result<Self> {
+        let source = match fs::read_to_string(Self::path()) {
+            Ok(source) => source,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(error),
+        };
+        Ok(Self::parse(&source))
+    }
+
+    // Lines that aren't a known id, such as achievements from newer versions, are skipped.
+    fn parse(source: &str) -> Self {
+        let unlocked = source
+            .lines()
+            .filter_map(|line| {
+                Achievement::ALL
+                    .into_iter()
+                    .find(|achievement| achievement.id() == line.trim())
+            })
+            .collect();
+        Self { unlocked }
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let ids: Vec<&str> = self
+            .unlocked
+            .iter()
+            .map(|achievement| achievement.id())
+            .collect();
+        fs::write(path, ids.join("\n") + "\n")
+    }
+
+    pub(crate) fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+}
+
+#[derive(Component)]
+struct ToastStack;
+
+#[derive(Component, Deref, DerefMut)]
+struct Toast(Timer);
+
+pub fn achievements_plugin(app: &mut App) {
+    let achievements = Achievements::load().unwrap_or_else(|error| {
+        warn!("failed to read achievements: {error}");
+        Achievements::default()
+    });
+    app.insert_resource(achievements)
+        .add_systems(Startup, spawn_toast_stack)
+        .add_systems(Update, expire_toasts)
+        .add_observer(on_unlock);
+}
+
+fn spawn_toast_stack(mut commands: Commands) {
+    commands.spawn((
+        ToastStack,
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(10),
+            right: px(10),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::End,
+            row_gap: px(8),
+            ..default()
+        },
+        GlobalZIndex(1),
+    ));
+}
+
+fn on_unlock(
+    unlock: On<Unlock>,
+    mut commands: Commands,
+    mut achievements: ResMut<Achievements>,
+    stack: Single<Entity, With<ToastStack>>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+    headless: Option<Res<HeadlessRun>>,
+) {
+    let achievement = unlock.0;
+    // Headless runs leave the player's save directory alone.
+    if headless.is_some() || achievements.is_unlocked(achievement) {
+        return;
+    }
+    achievements.unlocked.push(achievement);
+    if let Err(error) = achievements.save() {
+        warn!("failed to save achievements: {error}");
+    }
+
+    let text = locale.format(
+        "achievement-unlocked",
+        [("name", locale.get(achievement.name_id()).into())],
+    );
+    commands.entity(*stack).with_child((
+        Toast(Timer::from_seconds(TOAST_SECONDS, TimerMode::Once)),
+        theme.background_color(ThemeColor::Overlay),
+        Node {
+            padding: UiRect::axes(px(16), px(8)),
+            ..default()
+        },
+        children![(
+            Text::new(text),
+            TextFont {
+                font_size: 20.0,
+                ..default()
+            },
+            theme.text_color(ThemeColor::Text),
+        )],
+    ));
+}
+
+// Toasts keep counting down while the game is paused; they only report, never block.
+fn expire_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toast_query: Query<(Entity, &mut Toast)>,
+) {
+    for (entity, mut toast) in &mut toast_query {
+        if toast.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_unlocked_ids() {
+        let achievements = Achievements::parse("combo\n  first-try \n\nspeedrun\n");
+        assert_eq!(
+            achievements.unlocked,
+            [Achievement::Combo, Achievement::FirstTry]
+        );
+        assert!(achievements.is_unlocked(Achievement::Combo));
+        assert!(!achievements.is_unlocked(Achievement::Flawless));
+        assert!(Achievements::parse("").unlocked.is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use super::config::Config;
+use super::level::{Level, MAX_COLUMNS, MAX_ROWS};
+use super::GlobalGameState;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub(crate) const USAGE: &str = "\
+Usage: breakout [OPTIONS]
+
+Options:
+  --skip-splash         Start at the loading screen
+  --rows <N>            Start a game with N brick rows
+  --columns <N>         Start a game with N brick columns
+  --level <PATH>        Start a game on the level file at PATH
+  --seed <N>            Seed the random number generator of offline games
+  --windowed            Run in a window
+  --fullscreen          Run fullscreen
+  --config <PATH>       Read settings from PATH instead of breakout.cfg
+  --headless <TICKS>    Simulate TICKS game ticks without a window, then exit
+  -h, --help            Print this help";
+
+#[derive(Default)]
+pub(crate) struct Options {
+    pub skip_splash: bool,
+    pub rows: Option<usize>,
+    pub columns: Option<usize>,
+    pub level: Option<PathBuf>,
+    pub seed: Option<u64>,
+    pub fullscreen: Option<bool>,
+    pub config: Option<PathBuf>,
+    pub headless: Option<u64>,
+    pub help: bool,
+}
+
+impl Options {
+    pub(crate) fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for `{arg}`"))
+            };
+            match arg.as_str() {
+                "--skip-splash" => options.skip_splash = true,
+                "--rows" => options.rows = Some(parse(&value()?)?),
+                "--columns" => options.columns = Some(parse(&value()?)?),
+                "--level" => options.level = Some(value()?.into()),
+                "--seed" => options.seed = Some(parse(&value()?)?),
+                "--windowed" => options.fullscreen = Some(false),
+                "--fullscreen" => options.fullscreen = Some(true),
+                "--config" => options.config = Some(value()?.into()),
+                "--headless" => options.headless = Some(parse(&value()?)?),
+                "-h" | "--help" => options.help = true,
+                _ => return Err(format!("unknown option `{arg}`")),
+            }
+        }
+        Ok(options)
+    }
+
+    // Asking for a particular board, or a headless run, goes straight into a game.
+    pub(crate) fn initial_state(&self) -> GlobalGameState {
+        if self.headless.is_some()
+            || self.rows.is_some()
+            || self.columns.is_some()
+            || self.level.is_some()
+        {
+            GlobalGameState::Game
+        } else if self.skip_splash {
+            GlobalGameState::Loading
+        } else {
+            GlobalGameState::Splash
+        }
+    }
+
+    // Command line options take precedence over the config file.
+    pub(crate) fn apply(&self, config: &mut Config) -> Result<(), String> {
+        let settings = &mut config.settings;
+        if let Some(rows) = self.rows {
+            settings.brick_rows = rows.clamp(1, MAX_ROWS);
+        }
+        if let Some(columns) = self.columns {
+            settings.brick_columns = columns.clamp(1, MAX_COLUMNS);
+        }
+        if let Some(path) = &self.level {
+            let level = Level::load(path)
+                .map_err(|error| format!("failed to load {}: {error}", path.display()))?;
+            settings.level = Some(level);
+        }
+        if self.seed.is_some() {
+            settings.seed = self.seed;
+        }
+        if let Some(fullscreen) = self.fullscreen {
+            config.fullscreen = fullscreen;
+        }
+        Ok(())
+    }
+}
+
+fn parse<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_every_option() {
+        let options = parse_args(&[
+            "--skip-splash",
+            "--rows",
+            "4",
+            "--columns",
+            "12",
+            "--level",
+            "levels/one.txt",
+            "--seed",
+            "7",
+            "--windowed",
+            "--config",
+            "other.cfg",
+            "--headless",
+            "600",
+            "--help",
+        ])
+        .unwrap();
+        assert!(options.skip_splash);
+        assert_eq!(options.rows, Some(4));
+        assert_eq!(options.columns, Some(12));
+        assert_eq!(options.level, Some(PathBuf::from("levels/one.txt")));
+        assert_eq!(options.seed, Some(7));
+        assert_eq!(options.fullscreen, Some(false));
+        assert_eq!(options.config, Some(PathBuf::from("other.cfg")));
+        assert_eq!(options.headless, Some(600));
+        assert!(options.help);
+    }
+
+    #[test]
+    fn the_last_window_mode_wins() {
+        let options = parse_args(&["--windowed", "--fullscreen"]).unwrap();
+        assert_eq!(options.fullscreen, Some(true));
+    }
+
+    #[test]
+    fn rejects_unknown_options_and_bad_values() {
+        assert_eq!(
+            parse_args(&["--bricks"]).err().unwrap(),
+            "unknown option `--bricks`"
+        );
+        assert_eq!(
+            parse_args(&["--rows"]).err().unwrap(),
+            "missing value for `--rows`"
+        );
+        assert_eq!(
+            parse_args(&["--seed", "-1"]).err().unwrap(),
+            "invalid value `-1`"
+        );
+    }
+
+    #[test]
+    fn picks_the_initial_state() {
+        let state = |args: &[&str]| parse_args(args).unwrap().initial_state();
+        assert_eq!(state(&[]), GlobalGameState::Splash);
+        assert_eq!(state(&["--skip-splash"]), GlobalGameState::Loading);
+        assert_eq!(state(&["--rows", "3"]), GlobalGameState::Game);
+        assert_eq!(state(&["--headless", "10"]), GlobalGameState::Game);
+    }
+
+    #[test]
+    fn options_override_and_clamp_the_config() {
+        let mut config: Config = "rows = 5\ncolumns = 10\nfullscreen = true".parse().unwrap();
+        parse_args(&[
+            "--rows",
+            "50",
+            "--columns",
+            "0",
+            "--seed",
+            "3",
+            "--windowed",
+        ])
+        .unwrap()
+        .apply(&mut config)
+        .unwrap();
+        assert_eq!(config.settings.brick_rows, 10);
+        assert_eq!(config.settings.brick_columns, 1);
+        assert_eq!(config.settings.seed, Some(3));
+        assert!(!config.fullscreen);
+    }
+
+    #[test]
+    fn unset_options_leave_the_config_alone() {
+        let mut config: Config = "rows = 3\nfullscreen = true".parse().unwrap();
+        parse_args(&[]).unwrap().apply(&mut config).unwrap();
+        assert_eq!(config.settings.brick_rows, 3);
+        assert!(config.fullscreen);
+    }
+
+    #[test]
+    fn a_missing_level_file_is_an_error() {
+        let mut config = Config::default();
+        let error = parse_args(&["--level", "no/such/level.txt"])
+            .unwrap()
+            .apply(&mut config)
+            .unwrap_err();
+        assert!(error.starts_with("failed to load no/such/level.txt"));
+    }
+}
@@ -0,0 +1,171 @@
+use super::level::{MAX_COLUMNS, MAX_ROWS};
+use super::GameSettings;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+pub(crate) const DEFAULT_CONFIG: &str = "breakout.cfg";
+
+// A `key = value` file, one setting per line; `#` starts a comment line.
+//   rows = 5
+//   columns = 10
+//   countdown = 3
+//   time-limit = 0
+//   paddle-spin = 0.3
+//   brick-symbols = false
+//   fullscreen = false
+#[derive(Default)]
+pub(crate) struct Config {
+    pub settings: GameSettings,
+    pub fullscreen: bool,
+}
+
+impl Config {
+    // The default file is optional, an explicitly requested one is not.
+    pub(crate) fn load(path: Option<&Path>) -> io::Result<Self> {
+        match path {
+            Some(path) => fs::read_to_string(path)?.parse(),
+            None => match fs::read_to_string(DEFAULT_CONFIG) {
+                Ok(source) => source.parse(),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+                Err(error) => Err(error),
+            },
+        }
+    }
+}
+
+impl FromStr for Config {
+    type Err = io::Error;
+
+    fn from_str(source: &str) -> io::Result<Self> {
+        let invalid = |line: usize, message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {line}: {message}"),
+            )
+        };
+
+        let mut config = Self::default();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(invalid(
+                    index + 1,
+                    format!("expected `key = value`, found `{line}`"),
+                ));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let parsed = match key {
+                "rows" => parse(value)
+                    .map(|rows: usize| config.settings.brick_rows = rows.clamp(1, MAX_ROWS)),
+                "columns" => parse(value).map(|columns: usize| {
+                    config.settings.brick_columns = columns.clamp(1, MAX_COLUMNS)
+                }),
+                "countdown" => parse(value)
+                    .map(|seconds: usize| config.settings.countdown_seconds = seconds.min(5)),
+                "time-limit" => parse(value)
+                    .map(|minutes: usize| config.settings.time_limit_minutes = minutes.min(10)),
+                "paddle-spin" => parse(value).map(|spin| config.settings.paddle_spin = spin),
+                "brick-symbols" => {
+                    parse(value).map(|enabled| config.settings.brick_symbols = enabled)
+                }
+                "fullscreen" => parse(value).map(|enabled| config.fullscreen = enabled),
+                _ => Err(format!("unknown setting `{key}`")),
+            };
+            parsed.map_err(|message| invalid(index + 1, message))?;
+        }
+        Ok(config)
+    }
+}
+
+fn parse<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_every_setting() {
+        let config: Config = "\
+# A comment, then a blank line
+
+rows = 4
+columns=12
+countdown = 2
+time-limit = 3
+paddle-spin = 0.5
+brick-symbols = true
+fullscreen = true
+"
+        .parse()
+        .unwrap();
+        let settings = &config.settings;
+        assert_eq!(settings.brick_rows, 4);
+        assert_eq!(settings.brick_columns, 12);
+        assert_eq!(settings.countdown_seconds, 2);
+        assert_eq!(settings.time_limit_minutes, 3);
+        assert_eq!(settings.paddle_spin, 0.5);
+        assert!(settings.brick_symbols);
+        assert!(config.fullscreen);
+    }
+
+    #[test]
+    fn missing_settings_keep_their_defaults() {
+        let config: Config = "rows = 2".parse().unwrap();
+        let defaults = GameSettings::default();
+        assert_eq!(config.settings.brick_columns, defaults.brick_columns);
+        assert_eq!(
+            config.settings.countdown_seconds,
+            defaults.countdown_seconds
+        );
+        assert!(!config.fullscreen);
+    }
+
+    #[test]
+    fn clamps_out_of_range_values() {
+        let config: Config = "rows = 0\ncolumns = 99\ncountdown = 9\ntime-limit = 60"
+            .parse()
+            .unwrap();
+        assert_eq!(config.settings.brick_rows, 1);
+        assert_eq!(config.settings.brick_columns, 20);
+        assert_eq!(config.settings.countdown_seconds, 5);
+        assert_eq!(config.settings.time_limit_minutes, 10);
+    }
+
+    #[test]
+    fn later_lines_override_earlier_ones() {
+        let config: Config = "rows = 2\nrows = 7".parse().unwrap();
+        assert_eq!(config.settings.brick_rows, 7);
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_setting() {
+        let error = |source: &str| source.parse::<Config>().err().unwrap().to_string();
+        assert_eq!(
+            error("rows = 2\nspeed = 3"),
+            "line 2: unknown setting `speed`"
+        );
+        assert_eq!(error("\n\nrows = many"), "line 3: invalid value `many`");
+        assert_eq!(
+            error("fullscreen"),
+            "line 1: expected `key = value`, found `fullscreen`"
+        );
+    }
+
+    #[test]
+    fn an_explicit_config_file_must_exist() {
+        let error = Config::load(Some(Path::new("no/such/breakout.cfg")))
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
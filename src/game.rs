@@ -1,78 +1,741 @@
-use super::{GameSettings, GlobalGameState};
+use super::level::{BrickKind, Level};
+use super::locale::Locale;
+use super::menu::{spawn_button, spawn_setting_rows};
+use super::net::NetConfig;
+use super::theme::{Theme, ThemeColor};
+use super::{GameMode, GameSettings, GlobalGameState};
+use bevy::ecs::schedule::ScheduleLabel;
 use bevy::math::bounding::{Aabb2d, BoundingCircle, BoundingVolume, IntersectsVolume};
 use bevy::prelude::*;
+use bevy::window::WindowFocused;
+use rand::rngs::Xoshiro256PlusPlus;
+use rand::{RngExt, SeedableRng};
+
+pub(crate) use headless::HeadlessRun;
+pub(crate) use save::{has_saved_game, ContinueGame};
+
+#[cfg(feature = "debug")]
+mod console;
+#[cfg(feature = "debug")]
+mod debug;
+mod editor;
+mod endless;
+mod headless;
+mod motion;
+mod rollback;
+mod save;
+mod sprites;
+mod symbols;
+mod time_attack;
+mod tracking;
 
 const PADDLE_SPEED: f32 = 600.0;
 const PADDLE_WIDTH: f32 = 100.0;
+const PADDLE_HEIGHT: f32 = 22.0;
+const PADDLE_OFFSET: f32 = 50.0;
+const BRICK_AREA_GUTTER: f32 = 10.0;
+const BRICK_GAP: f32 = 5.0;
+const BRICK_HEIGHT: f32 = 20.0;
+const ARENA_GAP: f32 = 20.0;
+const GARBAGE_EVERY: u32 = 5;
 const BALL_RADIUS: f32 = 10.0;
 const BALL_SPEED: f32 = 300.0;
+const BALL_MAX_SPEED: f32 = 700.0;
+const BALL_MIN_VERTICAL: f32 = 0.3;
+const STALL_TIMEOUT: f32 = 8.0;
+const STALL_NUDGE_ANGLE: std::ops::Range<f32> = 0.15..0.35;
+const GAMEPAD_DEADZONE: f32 = 0.2;
+const STARTING_LIVES: u32 = 3;
+const BRICK_POINTS: u32 = 10;
+const PLAYER_KEYS: [[KeyCode; 2]; 2] = [
+    [KeyCode::KeyA, KeyCode::KeyD],
+    [KeyCode::ArrowLeft, KeyCode::ArrowRight],
+];
 
-#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, SubStates)]
+#[source(GlobalGameState = GlobalGameState::Game)]
 enum GameState {
     #[default]
     Ready,
+    Countdown,
     Play,
     Pause,
+    GameOver,
+}
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, SubStates)]
+#[source(GameState = GameState::Pause)]
+enum PauseMenuState {
+    #[default]
+    Main,
+    Settings,
 }
 
 #[derive(Component)]
-struct Paddle;
+struct PauseMenu;
 
 #[derive(Component)]
-struct Ball;
+enum PauseButton {
+    Resume,
+    Restart,
+    Settings,
+    Back,
+    Quit,
+}
+
+#[derive(Resource)]
+struct GameAssets {
+    rectangle: Handle<Mesh>,
+    circle: Handle<Mesh>,
+    paddle_materials: Vec<Handle<ColorMaterial>>,
+    ball_material: Handle<ColorMaterial>,
+    divider_material: Handle<ColorMaterial>,
+    brick_materials: Vec<Handle<ColorMaterial>>,
+    cracked_materials: Vec<Handle<ColorMaterial>>,
+    unbreakable_material: Handle<ColorMaterial>,
+}
+
+impl GameAssets {
+    fn brick_material(&self, brick: &Brick) -> Handle<ColorMaterial> {
+        match brick.kind {
+            BrickKind::Unbreakable => self.unbreakable_material.clone(),
+            _ if brick.hits < brick.kind.hits() => self.cracked_materials[brick.color].clone(),
+            _ => self.brick_materials[brick.color].clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ArenaLayout {
+    bounds: Rect,
+    columns: usize,
+    brick_width: f32,
+}
+
+impl ArenaLayout {
+    fn new(bounds: Rect, columns: usize) -> Self {
+        let brick_area_width =
+            bounds.width() - (BRICK_AREA_GUTTER * 2.0) - (BRICK_GAP * (columns as f32 - 1.0));
+        Self {
+            bounds,
+            columns,
+            brick_width: brick_area_width / columns as f32,
+        }
+    }
+
+    fn brick_position(&self, row: usize, column: usize) -> Vec3 {
+        let column_start = self.bounds.min.x + BRICK_AREA_GUTTER + self.brick_width / 2.0;
+        let row_start = self.bounds.max.y - BRICK_AREA_GUTTER - BRICK_HEIGHT / 2.0;
+        Vec3::new(
+            column_start + column as f32 * (self.brick_width + BRICK_GAP),
+            row_start - row as f32 * (BRICK_HEIGHT + BRICK_GAP),
+            0.0,
+        )
+    }
+
+    fn cell_at(&self, point: Vec2) -> Option<(usize, usize)> {
+        let origin = Vec2::new(
+            self.bounds.min.x + BRICK_AREA_GUTTER,
+            self.bounds.max.y - BRICK_AREA_GUTTER,
+        );
+        let offset = Vec2::new(point.x - origin.x, origin.y - point.y);
+        if offset.x < 0.0 || offset.y < 0.0 {
+            return None;
+        }
+        let column = (offset.x / (self.brick_width + BRICK_GAP)) as usize;
+        let row = (offset.y / (BRICK_HEIGHT + BRICK_GAP)) as usize;
+        (column < self.columns).then_some((row, column))
+    }
+
+    fn paddle_y(&self) -> f32 {
+        self.bounds.min.y + PADDLE_OFFSET
+    }
+
+    fn ball_start(&self) -> Vec3 {
+        Vec3::new(self.bounds.center().x, self.paddle_y() + 20.0, 0.0)
+    }
+}
+
+#[derive(Resource, Deref)]
+struct Arenas(Vec<ArenaLayout>);
+
+#[derive(Component, Clone, Copy, Deref)]
+struct Arena(usize);
+
+#[derive(Resource, Clone, Copy)]
+enum GameOutcome {
+    Lost,
+    Cleared,
+    Winner(usize),
+}
+
+// Where `GameTick` leaves the outcome instead of ending the game itself. Online games run
+// ahead on guessed input, so rollback only commits it once the frame it happened on is
+// confirmed; offline it is committed straight after the tick.
+#[derive(Resource, Default, Clone, Copy)]
+struct PendingOutcome(Option<GameOutcome>);
+
+#[derive(Resource, Default, Clone)]
+struct Garbage([u32; 2]);
+
+#[derive(Resource, Clone, Deref, DerefMut)]
+struct GameRng(Xoshiro256PlusPlus);
+
+#[derive(Resource, Default, Clone, Copy)]
+struct NextBrickId(u32);
+
+#[derive(Component, Clone, Copy, Eq, PartialEq, Hash)]
+struct BrickId(u32);
+
+#[derive(Resource, Default)]
+struct PaddleInputs([f32; 2]);
+
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct GameTick;
+
+#[derive(Component, Clone, Deref, DerefMut)]
+struct StallTimer(Timer);
+
+#[derive(Resource, Deref, DerefMut)]
+struct CountdownTimer(Timer);
 
 #[derive(Component)]
-struct Brick;
+struct CountdownText;
+
+#[derive(Resource, Clone, Deref, DerefMut)]
+struct Lives(Vec<u32>);
+
+#[derive(Resource, Default, Clone)]
+struct Scores([u32; 2]);
+
+#[derive(Component)]
+struct Hud;
+
+#[derive(Component)]
+struct GameOverOverlay;
+
+#[derive(Component)]
+struct Paddle {
+    player: usize,
+    min_x: f32,
+    max_x: f32,
+}
+
+#[derive(Component)]
+struct Ball;
+
+#[derive(Component, Clone, Copy)]
+struct LastHit(usize);
+
+#[derive(Component, Clone, Copy)]
+struct Brick {
+    kind: BrickKind,
+    color: usize,
+    hits: u32,
+}
+
+impl Brick {
+    fn new(kind: BrickKind, color: usize) -> Self {
+        Self {
+            kind,
+            color,
+            hits: kind.hits(),
+        }
+    }
+}
 
 #[derive(EntityEvent)]
 struct CollisionEvent {
     pub entity: Entity,
+    pub ball: Entity,
+    // Only the debug overlay looks at where the contact happened.
+    #[cfg_attr(not(feature = "debug"), allow(dead_code))]
+    pub point: Vec2,
     pub nudge: Vec2,
 }
 
+#[derive(Event)]
+struct BallLost {
+    pub ball: Entity,
+}
+
+#[derive(Event)]
+struct BrickBroken {
+    pub ball: Entity,
+}
+
+// Rebuilds the running game from scratch. Setting `GlobalGameState::Game` again is an
+// identity transition, which `DespawnOnExit` ignores, so the old entities are cleared here.
+#[derive(Event)]
+struct RestartGame;
+
+#[derive(Event)]
+struct PushBrickRow {
+    pub arena: usize,
+}
+
 impl Velocity {
     fn accelerate(&mut self) {
-        self.0 = (self.0 * 1.10).clamp_length_max(700.0)
+        self.0 = (self.0 * 1.10).clamp_length_max(BALL_MAX_SPEED)
+    }
+
+    fn enforce_min_vertical(&mut self, min_vertical: f32) {
+        let speed = self.length();
+        let direction = self.normalize_or_zero();
+        if direction.y.abs() >= min_vertical {
+            return;
+        }
+        let y = min_vertical.copysign(direction.y);
+        let x = (1.0 - y * y).sqrt() * direction.x.signum();
+        self.0 = Vec2::new(x, y) * speed;
     }
 }
 
-#[derive(Component, Deref, DerefMut, Debug)]
+#[derive(Component, Deref, DerefMut, Clone, Copy, Debug)]
 struct Velocity(Vec2);
 
 #[derive(Component)]
 struct Collider;
 
 pub fn game_plugin(app: &mut App) {
-    app.add_systems(OnEnter(GlobalGameState::Game), game_setup)
+    app.add_systems(Startup, load_game_assets)
+        .add_systems(OnEnter(GlobalGameState::Game), game_setup)
+        .add_systems(OnEnter(GameState::Ready), ready_overlay)
+        .add_systems(OnEnter(GameState::Countdown), countdown_setup)
         .add_systems(OnEnter(GameState::Pause), pause_overlay)
-        .init_state::<GameState>()
+        .add_systems(OnEnter(PauseMenuState::Main), pause_main_setup)
+        .add_systems(OnEnter(PauseMenuState::Settings), pause_settings_setup)
+        .add_systems(OnEnter(GameState::GameOver), game_over_overlay)
+        .add_sub_state::<GameState>()
+        .add_sub_state::<PauseMenuState>()
         .add_systems(
             Update,
             (
-                toggle_pause.run_if(in_state(GameState::Play).or(in_state(GameState::Pause))),
-                start_game.run_if(in_state(GlobalGameState::Game)),
+                toggle_pause
+                    .run_if(in_state(GameState::Play).or(in_state(GameState::Pause)))
+                    .run_if(offline),
+                start_game
+                    .run_if(in_state(GameState::Ready))
+                    .run_if(offline),
+                countdown.run_if(in_state(GameState::Countdown)),
+                pause_on_focus_lost
+                    .run_if(in_state(GameState::Play))
+                    .run_if(offline),
+                leave_online_game.run_if(in_state(GlobalGameState::Game).and(online)),
+                pause_button_system.run_if(in_state(GameState::Pause)),
+                return_to_menu.run_if(in_state(GameState::GameOver)),
+                update_hud.run_if(in_state(GlobalGameState::Game)),
+                apply_theme_materials,
             ),
         )
         .add_systems(
             FixedUpdate,
-            (move_paddle, apply_velocity, check_collision)
+            (read_local_input, run_game_tick, commit_outcome)
                 .chain()
-                .run_if(in_state(GameState::Play)),
+                .run_if(in_state(GameState::Play).and(offline)),
         )
-        .add_observer(on_collision);
+        .add_observer(on_restart_game)
+        .add_plugins((
+            game_tick_plugin,
+            rollback::rollback_plugin,
+            editor::editor_plugin,
+            sprites::sprites_plugin,
+            symbols::symbols_plugin,
+            headless::headless_plugin,
+            tracking::tracking_plugin,
+            save::save_plugin,
+            time_attack::time_attack_plugin,
+            endless::endless_plugin,
+        ));
+
+    #[cfg(feature = "debug")]
+    app.add_plugins((debug::debug_plugin, console::console_plugin));
 }
 
-fn game_setup(
+// The simulation itself: everything `GameTick` runs and the observers it triggers.
+fn game_tick_plugin(app: &mut App) {
+    app.add_systems(
+        GameTick,
+        (
+            move_paddle,
+            apply_velocity,
+            motion::move_bricks,
+            check_collision,
+            prevent_stall,
+            check_outcome,
+        )
+            .chain(),
+    )
+    .add_observer(on_collision)
+    .add_observer(on_ball_lost)
+    .add_observer(on_push_brick_row);
+}
+
+fn load_game_assets(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    theme: Res<Theme>,
+) {
+    commands.insert_resource(GameAssets {
+        rectangle: meshes.add(Rectangle::default()),
+        circle: meshes.add(Circle::default()),
+        paddle_materials: theme
+            .paddles
+            .iter()
+            .map(|color| materials.add(*color))
+            .collect(),
+        ball_material: materials.add(theme.ball),
+        divider_material: materials.add(theme.divider),
+        brick_materials: theme
+            .bricks
+            .iter()
+            .map(|color| materials.add(*color))
+            .collect(),
+        cracked_materials: theme
+            .bricks
+            .iter()
+            .map(|color| materials.add(color.lighter(0.2)))
+            .collect(),
+        unbreakable_material: materials.add(theme.unbreakable),
+    });
+}
+
+// Materials are shared by every entity, so recolouring them in place restyles the
+// whole arena without touching any entity.
+fn apply_theme_materials(
+    theme: Res<Theme>,
+    assets: Res<GameAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+
+    let mut recolor = |handle: &Handle<ColorMaterial>, color: Color| {
+        if let Some(material) = materials.get_mut(handle) {
+            material.color = color;
+        }
+    };
+    for (handle, color) in assets.paddle_materials.iter().zip(theme.paddles) {
+        recolor(handle, color);
+    }
+    recolor(&assets.ball_material, theme.ball);
+    recolor(&assets.divider_material, theme.divider);
+    for (handle, color) in assets.brick_materials.iter().zip(theme.bricks) {
+        recolor(handle, color);
+    }
+    for (handle, color) in assets.cracked_materials.iter().zip(theme.bricks) {
+        recolor(handle, color.lighter(0.2));
+    }
+    recolor(&assets.unbreakable_material, theme.unbreakable);
+}
+
+fn game_setup(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
     window: Single<&Window>,
     settings: Res<GameSettings>,
+    net_config: Option<Res<NetConfig>>,
+    theme: Res<Theme>,
 ) {
+    let seed = match net_config {
+        Some(config) if settings.online => config.seed,
+        _ => settings.seed.unwrap_or_else(rand::random),
+    };
+    let mut rng = GameRng(Xoshiro256PlusPlus::seed_from_u64(seed));
+    let mut next_brick_id = NextBrickId::default();
+
+    let window_bounds = Rect::from_center_size(Vec2::ZERO, window.size());
+    let arenas = match settings.mode {
+        GameMode::Solo | GameMode::Coop | GameMode::TimeAttack | GameMode::Endless => {
+            vec![window_bounds]
+        }
+        GameMode::Versus => vec![
+            Rect::new(
+                window_bounds.min.x,
+                window_bounds.min.y,
+                -ARENA_GAP / 2.0,
+                window_bounds.max.y,
+            ),
+            Rect::new(
+                ARENA_GAP / 2.0,
+                window_bounds.min.y,
+                window_bounds.max.x,
+                window_bounds.max.y,
+            ),
+        ],
+    };
+    let arenas: Vec<_> = arenas
+        .into_iter()
+        .map(|bounds| {
+            let columns = settings
+                .level
+                .as_ref()
+                .map_or(settings.brick_columns, |level| level.columns);
+            ArenaLayout::new(bounds, columns)
+        })
+        .collect();
+
+    commands.insert_resource(Lives(vec![STARTING_LIVES; arenas.len()]));
+    commands.insert_resource(Scores::default());
+    commands.insert_resource(Garbage::default());
+    commands.insert_resource(PendingOutcome::default());
+    commands.insert_resource(PaddleInputs::default());
+
+    commands.spawn((
+        Hud,
+        DespawnOnExit(GlobalGameState::Game),
+        Text::default(),
+        theme.text_color(ThemeColor::HudText),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(5.0),
+            left: px(10.0),
+            ..default()
+        },
+    ));
+
+    if settings.mode == GameMode::Versus {
+        commands.spawn((
+            DespawnOnExit(GlobalGameState::Game),
+            Mesh2d(assets.rectangle.clone()),
+            MeshMaterial2d(assets.divider_material.clone()),
+            Transform::from_scale(Vec3::new(ARENA_GAP / 2.0, window.height(), 1.0)),
+        ));
+    }
+
+    for (arena, layout) in arenas.iter().enumerate() {
+        let players = match settings.mode {
+            GameMode::Versus => arena..arena + 1,
+            GameMode::Solo | GameMode::Coop | GameMode::TimeAttack | GameMode::Endless => {
+                0..settings.mode.players()
+            }
+        };
+        let lane_width = layout.bounds.width() / players.len() as f32;
+
+        for (lane, player) in players.enumerate() {
+            let min_x = layout.bounds.min.x + lane_width * lane as f32 + PADDLE_WIDTH / 2.0;
+            let max_x = min_x + lane_width - PADDLE_WIDTH;
+
+            commands
+                .spawn((
+                    Paddle {
+                        player,
+                        min_x,
+                        max_x,
+                    },
+                    Arena(arena),
+                    Collider,
+                    Velocity(Vec2::ZERO),
+                    DespawnOnExit(GlobalGameState::Game),
+                    Mesh2d(assets.rectangle.clone()),
+                    MeshMaterial2d(assets.paddle_materials[player].clone()),
+                    Transform {
+                        translation: Vec3::new((min_x + max_x) / 2.0, layout.paddle_y(), 0.0),
+                        scale: Vec3::new(PADDLE_WIDTH, PADDLE_HEIGHT, 1.0),
+                        ..default()
+                    },
+                ))
+                .observe(on_paddle_collision);
+        }
+
+        commands
+            .spawn(ball_bundle(arena, layout, &assets))
+            .observe(on_ball_collision);
+
+        spawn_bricks(
+            &mut commands,
+            &assets,
+            &mut rng,
+            &mut next_brick_id,
+            layout,
+            arena,
+            settings.level.as_ref(),
+            settings.brick_rows,
+        );
+    }
+
+    commands.insert_resource(Arenas(arenas));
+    commands.insert_resource(rng);
+    commands.insert_resource(next_brick_id);
+}
+
+// Lays out the level if there is one, otherwise `rows` rows of random colours.
+#[allow(clippy::too_many_arguments)]
+fn spawn_bricks(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    rng: &mut GameRng,
+    next_brick_id: &mut NextBrickId,
+    layout: &ArenaLayout,
+    arena: usize,
+    level: Option<&Level>,
+    rows: usize,
+) {
+    match level {
+        Some(level) => spawn_level(commands, assets, next_brick_id, layout, arena, level),
+        None => {
+            let mut previous = None;
+            for row in 0..rows {
+                previous = Some(spawn_brick_row(
+                    commands,
+                    assets,
+                    rng,
+                    next_brick_id,
+                    layout,
+                    arena,
+                    row,
+                    previous,
+                ));
+            }
+        }
+    }
+}
+
+// Picks a random colour other than `neighbour`'s, so adjacent rows never match, and
+// returns it.
+#[allow(clippy::too_many_arguments)]
+fn spawn_brick_row(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    rng: &mut GameRng,
+    next_brick_id: &mut NextBrickId,
+    layout: &ArenaLayout,
+    arena: usize,
+    row: usize,
+    neighbour: Option<usize>,
+) -> usize {
+    let colors = assets.brick_materials.len();
+    let neighbour = neighbour.filter(|_| colors > 1);
+    let mut color = rng.random_range(0..colors - usize::from(neighbour.is_some()));
+    if neighbour.is_some_and(|neighbour| color >= neighbour) {
+        color += 1;
+    }
+    let brick = Brick::new(BrickKind::Normal, color);
+
+    for column in 0..layout.columns {
+        spawn_brick(
+            commands,
+            assets,
+            next_brick_id,
+            layout,
+            arena,
+            row,
+            column,
+            brick,
+        );
+    }
+    color
+}
+
+fn spawn_level(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    next_brick_id: &mut NextBrickId,
+    layout: &ArenaLayout,
+    arena: usize,
+    level: &Level,
+) {
+    for (row, column, brick) in level.bricks() {
+        let brick = Brick::new(brick.kind, brick.color % assets.brick_materials.len());
+        let entity = spawn_brick(
+            commands,
+            assets,
+            next_brick_id,
+            layout,
+            arena,
+            row,
+            column,
+            brick,
+        );
+        if let Some(motion) = motion::BrickMotion::for_level_brick(level, layout, row, column) {
+            commands
+                .entity(entity)
+                .insert((motion, Velocity(Vec2::ZERO)));
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_brick(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    next_brick_id: &mut NextBrickId,
+    layout: &ArenaLayout,
+    arena: usize,
+    row: usize,
+    column: usize,
+    brick: Brick,
+) -> Entity {
+    let entity = commands
+        .spawn(brick_bundle(
+            BrickId(next_brick_id.0),
+            brick,
+            Arena(arena),
+            assets,
+            Transform {
+                translation: layout.brick_position(row, column),
+                scale: Vec3::new(layout.brick_width, BRICK_HEIGHT, 1.0),
+                ..default()
+            },
+        ))
+        .observe(on_brick_collision)
+        .id();
+    next_brick_id.0 += 1;
+    entity
+}
+
+fn ball_bundle(arena: usize, layout: &ArenaLayout, assets: &GameAssets) -> impl Bundle {
+    (
+        Ball,
+        Arena(arena),
+        LastHit(arena),
+        Velocity(Vec2::new(BALL_SPEED, BALL_SPEED)),
+        StallTimer(Timer::from_seconds(STALL_TIMEOUT, TimerMode::Once)),
+        DespawnOnExit(GlobalGameState::Game),
+        Mesh2d(assets.circle.clone()),
+        MeshMaterial2d(assets.ball_material.clone()),
+        Transform {
+            translation: layout.ball_start(),
+            scale: Vec2::splat(BALL_RADIUS * 2.0).extend(1.0),
+            ..default()
+        },
+    )
+}
+
+fn brick_bundle(
+    id: BrickId,
+    brick: Brick,
+    arena: Arena,
+    assets: &GameAssets,
+    transform: Transform,
+) -> impl Bundle {
+    (
+        brick,
+        id,
+        arena,
+        Collider,
+        DespawnOnExit(GlobalGameState::Game),
+        Mesh2d(assets.rectangle.clone()),
+        MeshMaterial2d(assets.brick_material(&brick)),
+        transform,
+    )
+}
+
+fn opponent(arena: usize) -> usize {
+    (arena + 1) % 2
+}
+
+fn ready_overlay(mut commands: Commands, theme: Res<Theme>, locale: Res<Locale>) {
     commands
         .spawn((
             DespawnOnExit(GameState::Ready),
-            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            theme.background_color(ThemeColor::Overlay),
             Node {
                 width: percent(100),
                 height: percent(100),
@@ -83,118 +746,266 @@ fn game_setup(
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new("Press space when you are ready"),
-                TextColor(Color::WHITE),
+                locale.text("ready-prompt"),
+                theme.text_color(ThemeColor::Text),
                 TextFont {
                     font_size: 50.0,
                     ..default()
                 },
             ));
         });
+}
 
-    commands
-        .spawn((
-            Paddle,
-            Collider,
-            Mesh2d(meshes.add(Rectangle::default())),
-            MeshMaterial2d(materials.add(Color::srgb(0.6, 0.2, 0.2))),
-            Transform {
-                translation: Vec3::new(0.0, -window.height() / 2.0 + 50.0, 0.0),
-                scale: Vec3::new(PADDLE_WIDTH, 22.0, 1.0),
-                ..default()
-            },
-        ))
-        .observe(on_paddle_collision);
+fn game_over_overlay(
+    mut commands: Commands,
+    outcome: Res<GameOutcome>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+) {
+    let title = match *outcome {
+        GameOutcome::Lost => locale.get("outcome-lost"),
+        GameOutcome::Cleared => locale.get("outcome-cleared"),
+        GameOutcome::Winner(player) => {
+            locale.format("outcome-winner", [("player", (player + 1).into())])
+        }
+    };
 
     commands
         .spawn((
-            Ball,
-            Velocity(Vec2::new(BALL_SPEED, BALL_SPEED)),
-            Mesh2d(meshes.add(Circle::default())),
-            MeshMaterial2d(materials.add(Color::srgb(0.6, 0.1, 0.5))),
-            Transform {
-                translation: Vec3::new(0.0, -window.height() / 2.0 + 70.0, 0.0),
-                scale: Vec2::splat(BALL_RADIUS * 2.0).extend(1.0),
+            GameOverOverlay,
+            DespawnOnExit(GameState::GameOver),
+            theme.background_color(ThemeColor::Overlay),
+            Node {
+                width: percent(100),
+                height: percent(100),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: px(20.0),
                 ..default()
             },
         ))
-        .observe(on_ball_collision);
-
-    let brick_area_gutter = 10.0;
-    let brick_gap = 5.0;
-    let brick_height = 20.0;
-    let brick_area_width = window.width()
-        - (brick_area_gutter * 2.0)
-        - (brick_gap * (settings.brick_columns as f32 - 1.0));
-    let brick_width = brick_area_width / settings.brick_columns as f32;
-    let column_start = -window.width() / 2.0 + brick_area_gutter + brick_width / 2.0;
-    let row_start = window.height() / 2.0 - brick_area_gutter - brick_height / 2.0;
-
-    for row in 0..settings.brick_rows {
-        let r = rand::random_range(0.0..1.0);
-        let g = rand::random_range(0.0..1.0);
-        let b = rand::random_range(0.0..1.0);
-
-        for column in 0..settings.brick_columns {
-            let brick_x = column_start + column as f32 * (brick_width + brick_gap);
-            let brick_y = row_start - row as f32 * (brick_height + brick_gap);
-            commands
-                .spawn((
-                    Brick,
-                    Collider,
-                    Mesh2d(meshes.add(Rectangle::default())),
-                    MeshMaterial2d(materials.add(Color::srgb(r, g, b))),
-                    Transform {
-                        translation: Vec3::new(brick_x, brick_y, 0.0),
-                        scale: Vec3::new(brick_width, brick_height, 1.0),
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(title),
+                theme.text_color(ThemeColor::Text),
+                TextFont {
+                    font_size: 80.0,
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                locale.text("return-prompt"),
+                theme.text_color(ThemeColor::Text),
+                TextFont {
+                    font_size: 30.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+// Adds a column of result lines to the game over overlay, between the title and the
+// prompt to return to the menu.
+fn spawn_result_lines(commands: &mut Commands, overlay: Entity, lines: Vec<String>, theme: &Theme) {
+    let results = commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(8.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            for line in lines {
+                parent.spawn((
+                    Text::new(line),
+                    theme.text_color(ThemeColor::Text),
+                    TextFont {
+                        font_size: 26.0,
                         ..default()
                     },
-                ))
-                .observe(on_brick_collision);
-        }
+                ));
+            }
+        })
+        .id();
+    commands.entity(overlay).insert_children(1, &[results]);
+}
+
+fn return_to_menu(
+    mut game_state: ResMut<NextState<GlobalGameState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        game_state.set(GlobalGameState::Menu);
+    }
+}
+
+fn update_hud(
+    mut hud: Single<&mut Text, With<Hud>>,
+    lives: Res<Lives>,
+    scores: Res<Scores>,
+    settings: Res<GameSettings>,
+    locale: Res<Locale>,
+    time_attack: Option<Res<time_attack::TimeAttack>>,
+    endless: Option<Res<endless::Endless>>,
+) {
+    let clock_changed = time_attack.as_ref().is_some_and(|run| run.is_changed())
+        || endless.as_ref().is_some_and(|run| run.is_changed());
+    if !lives.is_changed() && !scores.is_changed() && !locale.is_changed() && !clock_changed {
+        return;
+    }
+
+    hud.0 = match settings.mode {
+        GameMode::Solo => locale.format(
+            "hud-solo",
+            [("lives", lives[0].into()), ("score", scores.0[0].into())],
+        ),
+        GameMode::Coop => locale.format(
+            "hud-coop",
+            [
+                ("lives", lives[0].into()),
+                ("first", scores.0[0].into()),
+                ("second", scores.0[1].into()),
+            ],
+        ),
+        GameMode::Versus => locale.format(
+            "hud-versus",
+            [
+                ("first-lives", lives[0].into()),
+                ("first-score", scores.0[0].into()),
+                ("second-lives", lives[1].into()),
+                ("second-score", scores.0[1].into()),
+            ],
+        ),
+        GameMode::TimeAttack => match time_attack {
+            Some(run) => run.hud_text(&locale, &settings, lives[0]),
+            None => String::new(),
+        },
+        GameMode::Endless => match endless {
+            Some(run) => run.hud_text(&locale, lives[0]),
+            None => String::new(),
+        },
+    };
+}
+
+fn offline(settings: Res<GameSettings>) -> bool {
+    !settings.online
+}
+
+fn online(settings: Res<GameSettings>) -> bool {
+    settings.online
+}
+
+fn leave_online_game(
+    mut game_state: ResMut<NextState<GlobalGameState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        game_state.set(GlobalGameState::Menu);
+    }
+}
+
+fn resume_state(settings: &GameSettings) -> GameState {
+    if settings.countdown_seconds > 0 {
+        GameState::Countdown
+    } else {
+        GameState::Play
     }
 }
 
 fn start_game(
     mut next_state: ResMut<NextState<GameState>>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    settings: Res<GameSettings>,
 ) {
     if keyboard_input.just_pressed(KeyCode::Space) {
+        next_state.set(resume_state(&settings));
+    }
+}
+
+fn countdown_setup(mut commands: Commands, settings: Res<GameSettings>, theme: Res<Theme>) {
+    commands.spawn((
+        DespawnOnExit(GameState::Countdown),
+        Node {
+            width: percent(100),
+            height: percent(100),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            CountdownText,
+            Text::new(settings.countdown_seconds.to_string()),
+            theme.text_color(ThemeColor::HudText),
+            TextFont {
+                font_size: 120.0,
+                ..default()
+            },
+        )],
+    ));
+    commands.insert_resource(CountdownTimer(Timer::from_seconds(
+        settings.countdown_seconds as f32,
+        TimerMode::Once,
+    )));
+}
+
+fn countdown(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut countdown_text: Single<&mut Text, With<CountdownText>>,
+    time: Res<Time>,
+    mut timer: ResMut<CountdownTimer>,
+) {
+    if timer.tick(time.delta()).is_finished() {
         next_state.set(GameState::Play);
     }
+    let remaining = timer.remaining_secs().ceil().max(1.0);
+    countdown_text.0 = format!("{remaining}");
 }
 
 fn toggle_pause(
     state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    settings: Res<GameSettings>,
 ) {
     if keyboard_input.just_pressed(KeyCode::Escape) {
         match state.get() {
             GameState::Play => next_state.set(GameState::Pause),
-            GameState::Pause => next_state.set(GameState::Play),
+            GameState::Pause => next_state.set(resume_state(&settings)),
             _ => {}
         }
     }
 }
 
-fn pause_overlay(mut commands: Commands) {
+fn pause_on_focus_lost(
+    mut focus_events: MessageReader<WindowFocused>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if focus_events.read().any(|event| !event.focused) {
+        next_state.set(GameState::Pause);
+    }
+}
+
+fn pause_overlay(mut commands: Commands, theme: Res<Theme>, locale: Res<Locale>) {
     commands
         .spawn((
+            PauseMenu,
             DespawnOnExit(GameState::Pause),
-            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            theme.background_color(ThemeColor::Overlay),
             Node {
                 width: percent(100),
                 height: percent(100),
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: px(20.0),
                 ..default()
             },
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new("PAUSED"),
-                TextColor(Color::WHITE),
+                locale.text("paused"),
+                theme.text_color(ThemeColor::Text),
                 TextFont {
                     font_size: 80.0,
                     ..default()
@@ -203,142 +1014,554 @@ fn pause_overlay(mut commands: Commands) {
         });
 }
 
-fn move_paddle(
-    mut paddle_transform: Single<&mut Transform, With<Paddle>>,
-    window: Single<&Window>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    time: Res<Time>,
-    // camera_query: Single<(&Camera, &GlobalTransform)>,
+fn pause_main_setup(
+    mut commands: Commands,
+    pause_menu: Single<Entity, With<PauseMenu>>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
 ) {
-    let paddle_half_width = PADDLE_WIDTH / 2.0;
-    let window_half_width = window.width() / 2.0;
-
-    //-------- Move paddle with moues -----------------
-    // let (camera, camera_transform) = *camera_query;
-    // let Some(cursor_position) = window.cursor_position() else {
-    //     return;
-    // };
-    // let Ok(point) = camera.viewport_to_world_2d(camera_transform, cursor_position) else {
-    //     return;
-    // };
-
-    // paddle_transform.translation.x = point.x.clamp(
-    //     -window_half_width + paddle_half_width,
-    //     window_half_width - paddle_half_width,
-    // )
-
-    //----------- Move paddle with key input -------------
+    commands
+        .entity(pause_menu.entity())
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    DespawnOnExit(PauseMenuState::Main),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: px(20.0),
+                        ..default()
+                    },
+                ))
+                .with_children(|col| {
+                    spawn_button(
+                        col,
+                        &theme,
+                        &locale,
+                        "pause-resume",
+                        200.0,
+                        30.0,
+                        PauseButton::Resume,
+                    );
+                    spawn_button(
+                        col,
+                        &theme,
+                        &locale,
+                        "pause-restart",
+                        200.0,
+                        30.0,
+                        PauseButton::Restart,
+                    );
+                    spawn_button(
+                        col,
+                        &theme,
+                        &locale,
+                        "menu-settings",
+                        200.0,
+                        30.0,
+                        PauseButton::Settings,
+                    );
+                    spawn_button(
+                        col,
+                        &theme,
+                        &locale,
+                        "pause-quit",
+                        200.0,
+                        30.0,
+                        PauseButton::Quit,
+                    );
+                });
+        });
+}
+
+fn pause_settings_setup(
+    mut commands: Commands,
+    pause_menu: Single<Entity, With<PauseMenu>>,
+    settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+) {
+    commands
+        .entity(pause_menu.entity())
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    DespawnOnExit(PauseMenuState::Settings),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: px(20.0),
+                        ..default()
+                    },
+                ))
+                .with_children(|col| {
+                    col.spawn((
+                        locale.text("settings-title"),
+                        TextFont {
+                            font_size: 36.0,
+                            ..default()
+                        },
+                        theme.text_color(ThemeColor::Text),
+                    ));
+                    spawn_setting_rows(col, &settings, &theme, &locale);
+                    col.spawn((
+                        locale.text("settings-restart-note"),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        theme.text_color(ThemeColor::MutedText),
+                    ));
+                    spawn_button(
+                        col,
+                        &theme,
+                        &locale,
+                        "menu-back",
+                        100.0,
+                        30.0,
+                        PauseButton::Back,
+                    );
+                });
+        });
+}
+
+fn pause_button_system(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &PauseButton), Changed<Interaction>>,
+    mut global_state: ResMut<NextState<GlobalGameState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut pause_menu_state: ResMut<NextState<PauseMenuState>>,
+    settings: Res<GameSettings>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match button {
+                PauseButton::Resume => game_state.set(resume_state(&settings)),
+                PauseButton::Restart => commands.trigger(RestartGame),
+                PauseButton::Settings => pause_menu_state.set(PauseMenuState::Settings),
+                PauseButton::Back => pause_menu_state.set(PauseMenuState::Main),
+                PauseButton::Quit => {
+                    commands.trigger(save::SaveGame);
+                    global_state.set(GlobalGameState::Menu);
+                }
+            }
+        }
+    }
+}
+
+fn on_restart_game(
+    _restart: On<RestartGame>,
+    mut commands: Commands,
+    scoped_query: Query<(Entity, &DespawnOnExit<GlobalGameState>)>,
+    mut global_state: ResMut<NextState<GlobalGameState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (entity, scope) in &scoped_query {
+        // Scoped children go with their parents, so some may already be gone.
+        if scope.0 == GlobalGameState::Game {
+            commands.entity(entity).try_despawn();
+        }
+    }
+    global_state.set(GlobalGameState::Game);
+    game_state.set(GameState::Ready);
+}
+
+fn player_direction(
+    keyboard_input: &ButtonInput<KeyCode>,
+    gamepad: Option<&Gamepad>,
+    keys: [KeyCode; 2],
+) -> f32 {
+    let [left, right] = keys;
     let mut direction = 0.0;
-    if keyboard_input.pressed(KeyCode::KeyA) {
+    if keyboard_input.pressed(left) {
         direction -= 1.0;
     }
-    if keyboard_input.pressed(KeyCode::KeyD) {
+    if keyboard_input.pressed(right) {
         direction += 1.0;
     }
 
-    let paddle_new_position =
-        paddle_transform.translation.x + direction * PADDLE_SPEED * time.delta_secs();
-    paddle_transform.translation.x = paddle_new_position.clamp(
-        -window_half_width + paddle_half_width,
-        window_half_width - paddle_half_width,
-    );
+    if let Some(gamepad) = gamepad {
+        let stick = gamepad.left_stick().x;
+        if stick.abs() > GAMEPAD_DEADZONE {
+            direction += stick;
+        }
+        if gamepad.pressed(GamepadButton::DPadLeft) {
+            direction -= 1.0;
+        }
+        if gamepad.pressed(GamepadButton::DPadRight) {
+            direction += 1.0;
+        }
+    }
+    direction.clamp(-1.0, 1.0)
 }
 
-fn apply_velocity(ball_query: Single<(&mut Transform, &Velocity), With<Ball>>, time: Res<Time>) {
-    let (mut ball_transform, ball_velocity) = ball_query.into_inner();
+fn read_local_input(
+    mut inputs: ResMut<PaddleInputs>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+) {
+    for (player, input) in inputs.0.iter_mut().enumerate() {
+        *input = player_direction(
+            &keyboard_input,
+            gamepads.iter().nth(player),
+            PLAYER_KEYS[player],
+        );
+    }
+}
 
-    ball_transform.translation +=
-        Vec3::new(ball_velocity.x, ball_velocity.y, 0.0) * time.delta_secs();
+fn run_game_tick(world: &mut World) {
+    world.run_schedule(GameTick);
 }
 
-fn check_collision(
+fn commit_outcome(
     mut commands: Commands,
-    window: Single<&Window>,
-    ball_query: Single<(&Transform, &mut Velocity), With<Ball>>,
-    paddle_query: Query<&Paddle>,
-    collider_query: Query<(Entity, &Transform), With<Collider>>,
+    mut pending: ResMut<PendingOutcome>,
+    mut next_state: ResMut<NextState<GameState>>,
 ) {
-    let (ball_transform, mut ball_velocity) = ball_query.into_inner();
-    let window_half_with = window.width() / 2.0;
-    let window_half_height = window.height() / 2.0;
+    if let Some(outcome) = pending.0.take() {
+        commands.insert_resource(outcome);
+        next_state.set(GameState::GameOver);
+    }
+}
 
-    if ball_transform.translation.x + BALL_RADIUS >= window_half_with {
-        ball_velocity.x = -ball_velocity.x.abs();
-    } else if ball_transform.translation.x - BALL_RADIUS <= -window_half_with {
-        ball_velocity.x = ball_velocity.x.abs();
+fn move_paddle(
+    mut paddle_query: Query<(&Paddle, &mut Transform, &mut Velocity)>,
+    inputs: Res<PaddleInputs>,
+    time: Res<Time>,
+    // window: Single<&Window>,
+    // camera_query: Single<(&Camera, &GlobalTransform)>,
+) {
+    for (paddle, mut paddle_transform, mut paddle_velocity) in &mut paddle_query {
+        //-------- Move paddle with moues -----------------
+        // let (camera, camera_transform) = *camera_query;
+        // let Some(cursor_position) = window.cursor_position() else {
+        //     return;
+        // };
+        // let Ok(point) = camera.viewport_to_world_2d(camera_transform, cursor_position) else {
+        //     return;
+        // };
+
+        // paddle_transform.translation.x = point.x.clamp(paddle.min_x, paddle.max_x)
+
+        //----------- Move paddle with player input -------------
+        let direction = inputs.0[paddle.player];
+
+        let paddle_old_position = paddle_transform.translation.x;
+        let paddle_new_position =
+            paddle_old_position + direction * PADDLE_SPEED * time.delta_secs();
+        paddle_transform.translation.x = paddle_new_position.clamp(paddle.min_x, paddle.max_x);
+        paddle_velocity.x =
+            (paddle_transform.translation.x - paddle_old_position) / time.delta_secs();
     }
+}
 
-    if ball_transform.translation.y + BALL_RADIUS >= window_half_height {
-        ball_velocity.y = -ball_velocity.y.abs()
-    } else if ball_transform.translation.y - BALL_RADIUS <= -window_half_height {
-        ball_velocity.y = ball_velocity.y.abs()
+fn apply_velocity(mut ball_query: Query<(&mut Transform, &Velocity), With<Ball>>, time: Res<Time>) {
+    for (mut ball_transform, ball_velocity) in &mut ball_query {
+        ball_transform.translation +=
+            Vec3::new(ball_velocity.x, ball_velocity.y, 0.0) * time.delta_secs();
     }
+}
 
-    let ball_bounding_circle =
-        BoundingCircle::new(ball_transform.translation.truncate(), BALL_RADIUS);
+fn check_collision(
+    mut commands: Commands,
+    arenas: Res<Arenas>,
+    mut ball_query: Query<(Entity, &Arena, &Transform, &mut Velocity, &mut LastHit), With<Ball>>,
+    paddle_query: Query<(&Paddle, &Velocity), Without<Ball>>,
+    settings: Res<GameSettings>,
+    collider_query: Query<(Entity, &Arena, &Transform), With<Collider>>,
+    moving_brick_query: Query<&Velocity, (With<Brick>, Without<Ball>)>,
+) {
+    for (ball, ball_arena, ball_transform, mut ball_velocity, mut last_hit) in &mut ball_query {
+        let bounds = arenas[**ball_arena].bounds;
 
-    for (entity, transform) in &collider_query {
-        let collision_entity_bounding_box = Aabb2d::new(
-            transform.translation.truncate(),
-            transform.scale.truncate() / 2.0,
-        );
+        if ball_transform.translation.x + BALL_RADIUS >= bounds.max.x {
+            ball_velocity.x = -ball_velocity.x.abs();
+        } else if ball_transform.translation.x - BALL_RADIUS <= bounds.min.x {
+            ball_velocity.x = ball_velocity.x.abs();
+        }
+
+        if ball_transform.translation.y + BALL_RADIUS >= bounds.max.y {
+            ball_velocity.y = -ball_velocity.y.abs()
+        } else if ball_transform.translation.y - BALL_RADIUS <= bounds.min.y {
+            commands.trigger(BallLost { ball });
+            continue;
+        }
 
-        if ball_bounding_circle.intersects(&collision_entity_bounding_box) {
-            let closest =
-                collision_entity_bounding_box.closest_point(ball_bounding_circle.center());
-            let offset = ball_bounding_circle.center() - closest;
-            let distance = offset.length();
-            let normal = if offset == Vec2::ZERO {
-                Vec2::Y
-                // (ball_bounding_circle.center() - collision_entity_bounding_box.center()).normalize_or_zero()
-            } else {
-                offset / distance
-                // offset.normalize()
-            };
-
-            let overlap = BALL_RADIUS - distance;
-            let nudge = normal * overlap;
-
-            if normal.x.abs() > normal.y.abs() {
-                ball_velocity.x = ball_velocity.x.abs() * normal.x.signum()
-            } else {
-                ball_velocity.y = ball_velocity.y.abs() * normal.y.signum()
+        let ball_bounding_circle =
+            BoundingCircle::new(ball_transform.translation.truncate(), BALL_RADIUS);
+
+        for (entity, arena, transform) in &collider_query {
+            if **arena != **ball_arena {
+                continue;
             }
 
-            if paddle_query.get(entity).is_ok() {
-                let paddle_relative_impact_point = (ball_bounding_circle.center().x
-                    - collision_entity_bounding_box.center().x)
-                    / (PADDLE_WIDTH / 2.0);
+            let collision_entity_bounding_box = Aabb2d::new(
+                transform.translation.truncate(),
+                transform.scale.truncate() / 2.0,
+            );
+
+            if ball_bounding_circle.intersects(&collision_entity_bounding_box) {
+                let closest =
+                    collision_entity_bounding_box.closest_point(ball_bounding_circle.center());
+                let offset = ball_bounding_circle.center() - closest;
+                let distance = offset.length();
+                let normal = if offset == Vec2::ZERO {
+                    Vec2::Y
+                    // (ball_bounding_circle.center() - collision_entity_bounding_box.center()).normalize_or_zero()
+                } else {
+                    offset / distance
+                    // offset.normalize()
+                };
 
-                let speed = ball_velocity.length();
-                let new_x = paddle_relative_impact_point * 0.8;
-                let new_direction = Vec2::new(new_x, 1.0).normalize();
+                let overlap = BALL_RADIUS - distance;
+                let nudge = normal * overlap;
 
-                ball_velocity.0 = new_direction * speed;
+                // Bouncing off a moving brick reflects the ball's velocity relative to the
+                // brick, so the brick's own velocity is carried over to the ball.
+                let collider_velocity = moving_brick_query
+                    .get(entity)
+                    .map_or(Vec2::ZERO, |velocity| velocity.0);
+                let mut relative_velocity = ball_velocity.0 - collider_velocity;
+                if normal.x.abs() > normal.y.abs() {
+                    relative_velocity.x = relative_velocity.x.abs() * normal.x.signum()
+                } else {
+                    relative_velocity.y = relative_velocity.y.abs() * normal.y.signum()
+                }
+                ball_velocity.0 =
+                    (relative_velocity + collider_velocity).clamp_length_max(BALL_MAX_SPEED);
+
+                if let Ok((paddle, paddle_velocity)) = paddle_query.get(entity) {
+                    last_hit.0 = paddle.player;
+
+                    let paddle_relative_impact_point = (ball_bounding_circle.center().x
+                        - collision_entity_bounding_box.center().x)
+                        / (PADDLE_WIDTH / 2.0);
+
+                    let speed = ball_velocity.length();
+                    let new_x = paddle_relative_impact_point * 0.8;
+                    let new_direction = Vec2::new(new_x, 1.0).normalize();
+                    let spin = Vec2::new(paddle_velocity.x * settings.paddle_spin, 0.0);
+
+                    ball_velocity.0 = (new_direction * speed + spin).normalize() * speed;
+                    ball_velocity.enforce_min_vertical(BALL_MIN_VERTICAL);
+                }
+
+                commands.trigger(CollisionEvent {
+                    entity,
+                    ball,
+                    point: closest,
+                    nudge,
+                });
             }
+        }
 
-            commands.trigger(CollisionEvent { entity, nudge });
+        ball_velocity.enforce_min_vertical(BALL_MIN_VERTICAL);
+    }
+}
+
+fn prevent_stall(
+    mut ball_query: Query<(&mut Velocity, &mut StallTimer), With<Ball>>,
+    mut rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    for (mut ball_velocity, mut stall_timer) in &mut ball_query {
+        if !stall_timer.tick(time.delta()).is_finished() {
+            continue;
         }
+
+        let angle = rng.random_range(STALL_NUDGE_ANGLE) * if rng.random() { 1.0 } else { -1.0 };
+        ball_velocity.0 = Vec2::from_angle(angle).rotate(ball_velocity.0);
+        ball_velocity.enforce_min_vertical(BALL_MIN_VERTICAL);
+        stall_timer.reset();
+    }
+}
+
+fn check_outcome(
+    mut commands: Commands,
+    arenas: Res<Arenas>,
+    settings: Res<GameSettings>,
+    brick_query: Query<(&Brick, &Arena, &Transform)>,
+    mut pending: ResMut<PendingOutcome>,
+) {
+    for (arena, layout) in arenas.iter().enumerate() {
+        let bricks: Vec<_> = brick_query
+            .iter()
+            .filter(|(_, brick_arena, _)| ***brick_arena == arena)
+            .collect();
+        let cleared = bricks
+            .iter()
+            .all(|(brick, ..)| brick.kind == BrickKind::Unbreakable);
+        let overrun = bricks.iter().any(|(_, _, transform)| {
+            transform.translation.y - BRICK_HEIGHT / 2.0 <= layout.paddle_y() + PADDLE_HEIGHT / 2.0
+        });
+
+        let outcome = match (settings.mode, cleared, overrun) {
+            (GameMode::Versus, true, _) => GameOutcome::Winner(arena),
+            (GameMode::Versus, _, true) => GameOutcome::Winner(opponent(arena)),
+            (GameMode::TimeAttack, true, _) => {
+                commands.trigger(time_attack::StageCleared);
+                return;
+            }
+            // An emptied board only brings the next row in sooner.
+            (GameMode::Endless, true, _) => continue,
+            (_, true, _) => GameOutcome::Cleared,
+            (_, _, true) => GameOutcome::Lost,
+            _ => continue,
+        };
+
+        pending.0.get_or_insert(outcome);
+        return;
     }
 }
 
-fn on_brick_collision(collision: On<CollisionEvent>, mut commands: Commands) {
+#[allow(clippy::too_many_arguments)]
+fn on_brick_collision(
+    collision: On<CollisionEvent>,
+    mut commands: Commands,
+    mut scores: ResMut<Scores>,
+    mut garbage: ResMut<Garbage>,
+    settings: Res<GameSettings>,
+    assets: Res<GameAssets>,
+    mut brick_query: Query<(
+        &Arena,
+        &mut Brick,
+        Option<&mut MeshMaterial2d<ColorMaterial>>,
+    )>,
+    ball_query: Query<&LastHit, With<Ball>>,
+) {
     let entity = collision.entity;
+    let Ok((&arena, mut brick, material)) = brick_query.get_mut(entity) else {
+        return;
+    };
+    if brick.kind == BrickKind::Unbreakable {
+        return;
+    }
+    brick.hits = brick.hits.saturating_sub(1);
+    if brick.hits > 0 {
+        if let Some(mut material) = material {
+            material.0 = assets.brick_material(&brick);
+        }
+        return;
+    }
     commands.entity(entity).despawn();
+    commands.trigger(BrickBroken {
+        ball: collision.ball,
+    });
+
+    if let Ok(last_hit) = ball_query.get(collision.ball) {
+        scores.0[last_hit.0] += BRICK_POINTS;
+    }
+
+    if settings.mode != GameMode::Versus {
+        return;
+    }
+    garbage.0[*arena] += 1;
+    if garbage.0[*arena].is_multiple_of(GARBAGE_EVERY) {
+        commands.trigger(PushBrickRow {
+            arena: opponent(*arena),
+        });
+    }
 }
 
 fn on_ball_collision(
     collision: On<CollisionEvent>,
-    mut ball_transform: Single<&mut Transform, With<Ball>>,
+    mut ball_query: Query<&mut Transform, With<Ball>>,
 ) {
-    ball_transform.translation += collision.nudge.extend(0.0)
+    if let Ok(mut ball_transform) = ball_query.get_mut(collision.ball) {
+        ball_transform.translation += collision.nudge.extend(0.0)
+    }
 }
 
 fn on_paddle_collision(_collision: On<CollisionEvent>) {}
 
 fn on_collision(
-    _collision: On<CollisionEvent>,
-    mut ball_velocity: Single<&mut Velocity, With<Ball>>,
+    collision: On<CollisionEvent>,
+    mut ball_query: Query<(&mut Velocity, &mut StallTimer), With<Ball>>,
+) {
+    if let Ok((mut ball_velocity, mut stall_timer)) = ball_query.get_mut(collision.ball) {
+        ball_velocity.accelerate();
+        stall_timer.reset();
+    }
+}
+
+fn on_ball_lost(
+    event: On<BallLost>,
+    mut lives: ResMut<Lives>,
+    arenas: Res<Arenas>,
+    settings: Res<GameSettings>,
+    mut ball_query: Query<(&Arena, &mut Transform, &mut Velocity, &mut LastHit), With<Ball>>,
+    mut pending: ResMut<PendingOutcome>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Ok((arena, mut ball_transform, mut ball_velocity, mut last_hit)) =
+        ball_query.get_mut(event.ball)
+    else {
+        return;
+    };
+    // Serve the ball as `ball_bundle` does, so bricks it breaks next aren't credited to
+    // whoever touched it before it was lost.
+    ball_transform.translation = arenas[**arena].ball_start();
+    ball_velocity.0 = Vec2::new(BALL_SPEED, BALL_SPEED);
+    last_hit.0 = **arena;
+
+    lives[**arena] = lives[**arena].saturating_sub(1);
+    if lives[**arena] > 0 {
+        if !settings.online {
+            next_state.set(GameState::Ready);
+        }
+        return;
+    }
+
+    let outcome = match settings.mode {
+        GameMode::Versus => GameOutcome::Winner(opponent(**arena)),
+        GameMode::Solo | GameMode::Coop | GameMode::TimeAttack | GameMode::Endless => {
+            GameOutcome::Lost
+        }
+    };
+    pending.0.get_or_insert(outcome);
+}
+
+fn on_push_brick_row(
+    event: On<PushBrickRow>,
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut rng: ResMut<GameRng>,
+    mut next_brick_id: ResMut<NextBrickId>,
+    arenas: Res<Arenas>,
+    mut brick_query: Query<(
+        &Brick,
+        &Arena,
+        &mut Transform,
+        Option<&mut motion::BrickMotion>,
+    )>,
 ) {
-    ball_velocity.accelerate();
+    // The new row lands on whatever is left of the top row. Moving bricks stray less than half
+    // a row from it, and if it has been cleared there is no colour to avoid.
+    let top_line = arenas[event.arena].brick_position(0, 0).y;
+    let top_color = brick_query
+        .iter()
+        .filter(|(_, arena, ..)| ***arena == event.arena)
+        .find(|(_, _, transform, _)| {
+            (transform.translation.y - top_line).abs() < (BRICK_HEIGHT + BRICK_GAP) / 2.0
+        })
+        .map(|(brick, ..)| brick.color);
+    let offset = Vec2::new(0.0, -(BRICK_HEIGHT + BRICK_GAP));
+    for (_, arena, mut transform, motion) in &mut brick_query {
+        if **arena == event.arena {
+            transform.translation += offset.extend(0.0);
+            if let Some(mut motion) = motion {
+                motion.shift(offset);
+            }
+        }
+    }
+    spawn_brick_row(
+        &mut commands,
+        &assets,
+        &mut rng,
+        &mut next_brick_id,
+        &arenas[event.arena],
+        event.arena,
+        0,
+        top_color,
+    );
 }
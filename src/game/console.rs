@@ -0,0 +1,277 @@
+use super::{
+    ball_bundle, on_ball_collision, Arenas, Ball, Brick, GameAssets, GameRng, GameState, Lives,
+    RestartGame, Velocity,
+};
+use crate::level::{BrickKind, Level};
+use crate::theme::{Theme, ThemeColor};
+use crate::{GameSettings, GlobalGameState};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::{ButtonState, InputSystems};
+use bevy::prelude::*;
+use bevy::ui::UiSystems;
+use rand::rngs::Xoshiro256PlusPlus;
+use rand::SeedableRng;
+use std::str::FromStr;
+
+const HISTORY_LINES: usize = 12;
+const HELP: &str = "spawn_ball | set_speed <speed> | clear_bricks | load_level <name> | \
+lives <count> | seed <seed> | state <menu|editor|game|ready|play|pause|gameover>";
+
+#[derive(Resource, Default)]
+struct Console {
+    open: bool,
+    input: String,
+    history: Vec<String>,
+}
+
+impl Console {
+    fn print(&mut self, line: impl Into<String>) {
+        self.history.push(line.into());
+        let overflow = self.history.len().saturating_sub(HISTORY_LINES);
+        self.history.drain(..overflow);
+    }
+}
+
+#[derive(Component)]
+struct ConsolePanel;
+
+pub(super) fn console_plugin(app: &mut App) {
+    app.init_resource::<Console>()
+        .add_systems(Startup, spawn_console)
+        .add_systems(
+            PreUpdate,
+            read_console_input
+                .after(InputSystems)
+                .before(UiSystems::Focus),
+        )
+        .add_systems(Update, update_console);
+}
+
+fn spawn_console(mut commands: Commands, theme: Res<Theme>) {
+    commands.spawn((
+        ConsolePanel,
+        Text::default(),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        theme.text_color(ThemeColor::Text),
+        theme.background_color(ThemeColor::Overlay),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(0),
+            width: percent(100),
+            padding: UiRect::all(px(8)),
+            ..default()
+        },
+        GlobalZIndex(i32::MAX - 1),
+        Visibility::Hidden,
+    ));
+}
+
+// The backquote key opens the console. While it is open it swallows the keyboard so typing
+// doesn't also move paddles or drive menus.
+fn read_console_input(
+    mut commands: Commands,
+    mut console: ResMut<Console>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut key_events: MessageReader<KeyboardInput>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Backquote) {
+        console.open = !console.open;
+    }
+    if !console.open {
+        key_events.clear();
+        return;
+    }
+
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed || event.key_code == KeyCode::Backquote {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                console.print(format!("> {line}"));
+                commands.queue(move |world: &mut World| {
+                    let output = run_command(world, &line);
+                    let mut console = world.resource_mut::<Console>();
+                    match output {
+                        Ok(output) if output.is_empty() => {}
+                        Ok(output) => console.print(output),
+                        Err(error) => console.print(format!("error: {error}")),
+                    }
+                });
+            }
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Escape => console.open = false,
+            _ => {
+                if let Some(text) = &event.text {
+                    console
+                        .input
+                        .extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+    keyboard_input.reset_all();
+}
+
+fn update_console(
+    console: Res<Console>,
+    panel: Single<(&mut Text, &mut Visibility), With<ConsolePanel>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+    let (mut text, mut visibility) = panel.into_inner();
+    *visibility = if console.open {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    text.0 = console
+        .history
+        .iter()
+        .map(String::as_str)
+        .chain([format!("> {}_", console.input).as_str()])
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
+fn run_command(world: &mut World, line: &str) -> Result<String, String> {
+    let mut words = line.split_whitespace();
+    let Some(command) = words.next() else {
+        return Ok(String::new());
+    };
+    let mut arg = || {
+        words
+            .next()
+            .ok_or_else(|| format!("`{command}` needs an argument"))
+    };
+
+    match command {
+        "help" => Ok(HELP.to_string()),
+        "spawn_ball" => spawn_ball(world),
+        "set_speed" => set_speed(world, parse(arg()?)?),
+        "clear_bricks" => clear_bricks(world),
+        "load_level" => load_level(world, arg()?),
+        "lives" => set_lives(world, parse(arg()?)?),
+        "seed" => set_seed(world, parse(arg()?)?),
+        "state" => set_state(world, arg()?),
+        _ => Err(format!("unknown command `{command}`, try `help`")),
+    }
+}
+
+fn parse<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}`"))
+}
+
+fn require_game(world: &World) -> Result<(), String> {
+    if *world.resource::<State<GlobalGameState>>().get() == GlobalGameState::Game {
+        Ok(())
+    } else {
+        Err("not in a game".to_string())
+    }
+}
+
+fn spawn_ball(world: &mut World) -> Result<String, String> {
+    require_game(world)?;
+    let layout = world.resource::<Arenas>()[0];
+    let bundle = ball_bundle(0, &layout, world.resource::<GameAssets>());
+    world.spawn(bundle).observe(on_ball_collision);
+    Ok("spawned a ball".to_string())
+}
+
+fn set_speed(world: &mut World, speed: f32) -> Result<String, String> {
+    require_game(world)?;
+    let mut ball_query = world.query_filtered::<&mut Velocity, With<Ball>>();
+    for mut velocity in ball_query.iter_mut(world) {
+        velocity.0 = velocity.normalize_or(Vec2::ONE.normalize()) * speed;
+    }
+    Ok(format!("ball speed set to {speed}"))
+}
+
+fn clear_bricks(world: &mut World) -> Result<String, String> {
+    require_game(world)?;
+    let mut brick_query = world.query::<(Entity, &Brick)>();
+    let bricks: Vec<Entity> = brick_query
+        .iter(world)
+        .filter(|(_, brick)| brick.kind != BrickKind::Unbreakable)
+        .map(|(entity, _)| entity)
+        .collect();
+    for &brick in &bricks {
+        world.despawn(brick);
+    }
+    Ok(format!("cleared {} bricks", bricks.len()))
+}
+
+fn load_level(world: &mut World, name: &str) -> Result<String, String> {
+    let path = Level::path(name);
+    let level = Level::load(&path).map_err(|error| format!("{}: {error}", path.display()))?;
+    world.resource_mut::<GameSettings>().level = Some(level);
+    start_game(world);
+    Ok(format!("loaded {}", path.display()))
+}
+
+// A game already in progress has to be torn down before it can be set up again.
+fn start_game(world: &mut World) {
+    if require_game(world).is_ok() {
+        world.trigger(RestartGame);
+    } else {
+        world
+            .resource_mut::<NextState<GlobalGameState>>()
+            .set(GlobalGameState::Game);
+    }
+}
+
+fn set_lives(world: &mut World, count: u32) -> Result<String, String> {
+    require_game(world)?;
+    for lives in world.resource_mut::<Lives>().iter_mut() {
+        *lives = count;
+    }
+    Ok(format!("lives set to {count}"))
+}
+
+// Reseeds the running game and every offline game after it.
+fn set_seed(world: &mut World, seed: u64) -> Result<String, String> {
+    world.resource_mut::<GameSettings>().seed = Some(seed);
+    if let Some(mut rng) = world.get_resource_mut::<GameRng>() {
+        rng.0 = Xoshiro256PlusPlus::seed_from_u64(seed);
+    }
+    Ok(format!("seed set to {seed}"))
+}
+
+fn set_state(world: &mut World, name: &str) -> Result<String, String> {
+    let global_state = match name {
+        "menu" => Some(GlobalGameState::Menu),
+        "editor" => Some(GlobalGameState::Editor),
+        "game" => Some(GlobalGameState::Game),
+        _ => None,
+    };
+    if let Some(state) = global_state {
+        if state == GlobalGameState::Game {
+            start_game(world);
+        } else {
+            world
+                .resource_mut::<NextState<GlobalGameState>>()
+                .set(state);
+        }
+        return Ok(format!("switching to {name}"));
+    }
+
+    let game_state = match name {
+        "ready" => GameState::Ready,
+        "play" => GameState::Play,
+        "pause" => GameState::Pause,
+        "gameover" => GameState::GameOver,
+        _ => return Err(format!("unknown state `{name}`")),
+    };
+    require_game(world)?;
+    world.resource_mut::<NextState<GameState>>().set(game_state);
+    Ok(format!("switching to {name}"))
+}
@@ -0,0 +1,185 @@
+use super::{Arenas, Ball, Collider, CollisionEvent, Velocity, BALL_RADIUS};
+use bevy::app::FixedMain;
+use bevy::color::palettes::css::{AQUA, LIME, ORANGE, RED, YELLOW};
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use bevy::time::TimeSystems;
+
+// How far ahead the velocity arrow points.
+const VELOCITY_ARROW_SECONDS: f32 = 0.1;
+const TIME_SCALES: [f32; 6] = [0.1, 0.25, 0.5, 1.0, 2.0, 4.0];
+
+#[derive(Resource, Default)]
+struct DebugOverlay {
+    visible: bool,
+    last_contact: Option<Contact>,
+}
+
+struct Contact {
+    point: Vec2,
+    nudge: Vec2,
+}
+
+#[derive(Component)]
+struct DebugReadout;
+
+pub(super) fn debug_plugin(app: &mut App) {
+    if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+        app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+    }
+    app.init_resource::<DebugOverlay>()
+        .add_systems(Startup, spawn_readout)
+        .add_systems(First, time_controls.after(TimeSystems))
+        .add_systems(
+            Update,
+            (
+                toggle_overlay,
+                (draw_colliders, draw_contact, update_readout).run_if(overlay_visible),
+            )
+                .chain(),
+        )
+        .add_observer(record_contact);
+}
+
+fn overlay_visible(overlay: Res<DebugOverlay>) -> bool {
+    overlay.visible
+}
+
+fn spawn_readout(mut commands: Commands) {
+    commands.spawn((
+        DebugReadout,
+        Text::default(),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(LIME.into()),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(8),
+            right: px(8),
+            ..default()
+        },
+        GlobalZIndex(i32::MAX),
+        Visibility::Hidden,
+    ));
+}
+
+fn toggle_overlay(
+    mut overlay: ResMut<DebugOverlay>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut readout: Single<&mut Visibility, With<DebugReadout>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F3) {
+        overlay.visible = !overlay.visible;
+        **readout = if overlay.visible {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+// F5 freezes the game clock, F6 then advances it by one fixed tick, and F7/F8 slow it
+// down or speed it up.
+fn time_controls(world: &mut World) {
+    let keyboard_input = world.resource::<ButtonInput<KeyCode>>();
+    let freeze = keyboard_input.just_pressed(KeyCode::F5);
+    let step = keyboard_input.just_pressed(KeyCode::F6);
+    let scale_step = match (
+        keyboard_input.just_pressed(KeyCode::F7),
+        keyboard_input.just_pressed(KeyCode::F8),
+    ) {
+        (true, false) => -1,
+        (false, true) => 1,
+        _ => 0,
+    };
+
+    let mut time = world.resource_mut::<Time<Virtual>>();
+    if freeze {
+        if time.is_paused() {
+            time.unpause();
+        } else {
+            time.pause();
+        }
+    }
+    if scale_step != 0 {
+        let current = TIME_SCALES
+            .iter()
+            .position(|scale| *scale >= time.relative_speed())
+            .unwrap_or(TIME_SCALES.len() - 1);
+        let index = current
+            .saturating_add_signed(scale_step)
+            .min(TIME_SCALES.len() - 1);
+        time.set_relative_speed(TIME_SCALES[index]);
+    }
+
+    // A frozen clock never accumulates a fixed timestep, so the step runs the fixed
+    // schedules directly, with the same `Time` they would normally see.
+    if step && time.is_paused() {
+        let fixed = world.resource::<Time<Fixed>>().as_generic();
+        *world.resource_mut::<Time>() = fixed;
+        world.run_schedule(FixedMain);
+        let virtual_time = world.resource::<Time<Virtual>>().as_generic();
+        *world.resource_mut::<Time>() = virtual_time;
+    }
+}
+
+fn record_contact(collision: On<CollisionEvent>, mut overlay: ResMut<DebugOverlay>) {
+    overlay.last_contact = Some(Contact {
+        point: collision.point,
+        nudge: collision.nudge,
+    });
+}
+
+// Mirrors the volumes `check_collision` tests against.
+fn draw_colliders(
+    mut gizmos: Gizmos,
+    arenas: Option<Res<Arenas>>,
+    collider_query: Query<&Transform, With<Collider>>,
+    ball_query: Query<(&Transform, &Velocity), With<Ball>>,
+) {
+    for arena in arenas.iter().flat_map(|arenas| arenas.iter()) {
+        gizmos.rect_2d(arena.bounds.center(), arena.bounds.size(), AQUA);
+    }
+    for transform in &collider_query {
+        gizmos.rect_2d(
+            transform.translation.truncate(),
+            transform.scale.truncate(),
+            LIME,
+        );
+    }
+    for (transform, velocity) in &ball_query {
+        let center = transform.translation.truncate();
+        gizmos.circle_2d(center, BALL_RADIUS, LIME);
+        gizmos.arrow_2d(center, center + velocity.0 * VELOCITY_ARROW_SECONDS, YELLOW);
+    }
+}
+
+fn draw_contact(mut gizmos: Gizmos, overlay: Res<DebugOverlay>) {
+    let Some(contact) = &overlay.last_contact else {
+        return;
+    };
+    gizmos.cross_2d(contact.point, 6.0, RED);
+    if contact.nudge != Vec2::ZERO {
+        gizmos.arrow_2d(contact.point, contact.point + contact.nudge, ORANGE);
+    }
+}
+
+fn update_readout(
+    diagnostics: Res<DiagnosticsStore>,
+    time: Res<Time<Virtual>>,
+    entity_query: Query<()>,
+    mut readout: Single<&mut Text, With<DebugReadout>>,
+) {
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    let frozen = if time.is_paused() { " (frozen)" } else { "" };
+    readout.0 = format!(
+        "{fps:.0} fps\n{} entities\n{}x speed{frozen}",
+        entity_query.iter().count(),
+        time.relative_speed(),
+    );
+}
@@ -0,0 +1,478 @@
+use super::symbols::{brick_color, brick_symbol, symbol_bundle, symbol_color};
+use super::{ArenaLayout, Brick, GameAssets, BRICK_HEIGHT};
+use crate::level::{BrickKind, Level, LevelBrick, RowMotion, MAX_ROWS};
+use crate::locale::Locale;
+use crate::theme::{Theme, ThemeColor, BRICK_COLOR_COUNT};
+use crate::{GameMode, GameSettings, GlobalGameState};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+const DEFAULT_LEVEL_NAME: &str = "custom";
+const COLOR_KEYS: [KeyCode; 10] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::Digit0,
+];
+const KIND_KEYS: [(KeyCode, BrickKind); 3] = [
+    (KeyCode::KeyN, BrickKind::Normal),
+    (KeyCode::KeyT, BrickKind::Tough),
+    (KeyCode::KeyU, BrickKind::Unbreakable),
+];
+
+// Kept between visits so designers can hop into a test game and come back to their work.
+#[derive(Resource)]
+struct Editor {
+    level: Level,
+    // The file the level is saved to, without the directory or extension.
+    name: String,
+    // The new name while the designer is typing one.
+    naming: Option<String>,
+    brush: LevelBrick,
+    status: String,
+}
+
+#[derive(Resource)]
+struct EditorGrid {
+    layout: ArenaLayout,
+    rows: usize,
+}
+
+#[derive(Resource)]
+struct EditorAssets {
+    empty_material: Handle<ColorMaterial>,
+}
+
+#[derive(Component)]
+struct EditorCell {
+    row: usize,
+    column: usize,
+}
+
+#[derive(Component)]
+struct EditorHud;
+
+pub(super) fn editor_plugin(app: &mut App) {
+    app.add_systems(Startup, load_editor_assets)
+        .add_systems(OnEnter(GlobalGameState::Editor), editor_setup)
+        .add_systems(OnExit(GlobalGameState::Editor), |mut commands: Commands| {
+            commands.remove_resource::<EditorGrid>()
+        })
+        .add_systems(
+            Update,
+            (
+                edit_level_name,
+                select_brush,
+                paint_cells,
+                cycle_row_motion,
+                editor_shortcuts,
+                sync_grid,
+                update_editor_hud,
+            )
+                .chain()
+                .run_if(in_state(GlobalGameState::Editor)),
+        )
+        .add_systems(Update, apply_theme_materials);
+}
+
+fn load_editor_assets(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    theme: Res<Theme>,
+) {
+    commands.insert_resource(EditorAssets {
+        empty_material: materials.add(theme.empty_cell),
+    });
+}
+
+fn apply_theme_materials(
+    theme: Res<Theme>,
+    editor_assets: Res<EditorAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+    if let Some(material) = materials.get_mut(&editor_assets.empty_material) {
+        material.color = theme.empty_cell;
+    }
+}
+
+fn editor_setup(
+    mut commands: Commands,
+    editor: Option<Res<Editor>>,
+    settings: Res<GameSettings>,
+    theme: Res<Theme>,
+) {
+    if editor.is_none() {
+        commands.insert_resource(Editor {
+            level: Level::new(MAX_ROWS, settings.brick_columns),
+            name: DEFAULT_LEVEL_NAME.to_string(),
+            naming: None,
+            brush: LevelBrick {
+                kind: BrickKind::Normal,
+                color: 0,
+            },
+            status: String::new(),
+        });
+    }
+
+    commands.spawn((
+        EditorHud,
+        DespawnOnExit(GlobalGameState::Editor),
+        Text::default(),
+        theme.text_color(ThemeColor::HudText),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(5.0),
+            left: px(10.0),
+            ..default()
+        },
+    ));
+}
+
+// F2 starts typing a new level name; Enter keeps it and Escape drops it. While typing, the
+// editor's other shortcuts are swallowed.
+fn edit_level_name(
+    mut editor: ResMut<Editor>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut key_events: MessageReader<KeyboardInput>,
+) {
+    if editor.naming.is_none() {
+        key_events.clear();
+        if keyboard_input.just_pressed(KeyCode::F2) {
+            editor.naming = Some(editor.name.clone());
+            keyboard_input.reset_all();
+        }
+        return;
+    }
+
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                if let Some(name) = editor.naming.take().filter(|name| !name.is_empty()) {
+                    editor.name = name;
+                }
+            }
+            Key::Escape => editor.naming = None,
+            Key::Backspace => {
+                if let Some(name) = &mut editor.naming {
+                    name.pop();
+                }
+            }
+            _ => {
+                // Names become file names, so keep to characters that are safe on any system.
+                if let (Some(text), Some(name)) = (&event.text, &mut editor.naming) {
+                    name.extend(
+                        text.chars()
+                            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_')),
+                    );
+                }
+            }
+        }
+    }
+    keyboard_input.reset_all();
+}
+
+fn select_brush(mut editor: ResMut<Editor>, keyboard_input: Res<ButtonInput<KeyCode>>) {
+    if keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    if let Some(color) = COLOR_KEYS
+        .iter()
+        .position(|key| keyboard_input.just_pressed(*key))
+    {
+        editor.brush.color = color % BRICK_COLOR_COUNT;
+    }
+    if let Some((_, kind)) = KIND_KEYS
+        .iter()
+        .find(|(key, _)| keyboard_input.just_pressed(*key))
+    {
+        editor.brush.kind = *kind;
+    }
+}
+
+fn hovered_cell(
+    grid: &EditorGrid,
+    window: &Window,
+    (camera, camera_transform): (&Camera, &GlobalTransform),
+) -> Option<(usize, usize)> {
+    let point = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())?;
+    grid.layout
+        .cell_at(point)
+        .filter(|(row, _)| *row < grid.rows)
+}
+
+fn paint_cells(
+    mut editor: ResMut<Editor>,
+    grid: Option<Res<EditorGrid>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    window: Single<&Window>,
+    camera_query: Single<(&Camera, &GlobalTransform)>,
+) {
+    let Some((row, column)) = grid.and_then(|grid| hovered_cell(&grid, &window, *camera_query))
+    else {
+        return;
+    };
+
+    let cell = editor.level.rows[row][column];
+    let brush = editor.brush;
+    let painted = if mouse_input.pressed(MouseButton::Right) {
+        None
+    } else if mouse_input.pressed(MouseButton::Left) {
+        if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            // Recolour keeps the brick type and leaves empty cells alone.
+            cell.map(|brick| LevelBrick {
+                color: brush.color,
+                ..brick
+            })
+        } else {
+            Some(brush)
+        }
+    } else {
+        return;
+    };
+
+    if painted != cell {
+        editor.level.rows[row][column] = painted;
+    }
+}
+
+// M steps the hovered row through static and each motion in turn.
+fn cycle_row_motion(
+    mut editor: ResMut<Editor>,
+    grid: Option<Res<EditorGrid>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    locale: Res<Locale>,
+    window: Single<&Window>,
+    camera_query: Single<(&Camera, &GlobalTransform)>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyM) {
+        return;
+    }
+    let Some((row, _)) = grid.and_then(|grid| hovered_cell(&grid, &window, *camera_query)) else {
+        return;
+    };
+
+    let next = match editor.level.motions.get(&row) {
+        None => Some(RowMotion::ALL[0]),
+        Some(motion) => RowMotion::ALL
+            .iter()
+            .position(|candidate| candidate == motion)
+            .and_then(|index| RowMotion::ALL.get(index + 1))
+            .copied(),
+    };
+    let name = match next {
+        Some(motion) => {
+            editor.level.motions.insert(row, motion);
+            locale.get(motion.message_id())
+        }
+        None => {
+            editor.level.motions.remove(&row);
+            locale.get("motion-none")
+        }
+    };
+    editor.status = locale.format(
+        "editor-motion",
+        [("row", (row + 1).into()), ("motion", name.into())],
+    );
+}
+
+fn editor_shortcuts(
+    mut editor: ResMut<Editor>,
+    mut settings: ResMut<GameSettings>,
+    mut game_state: ResMut<NextState<GlobalGameState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    locale: Res<Locale>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        game_state.set(GlobalGameState::Menu);
+        return;
+    }
+    if keyboard_input.just_pressed(KeyCode::Enter) {
+        settings.mode = GameMode::Solo;
+        settings.online = false;
+        settings.level = Some(editor.level.clone());
+        game_state.set(GlobalGameState::Game);
+        return;
+    }
+
+    if !keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyS) {
+        let path = Level::path(&editor.name);
+        let display = path.display().to_string();
+        editor.status = match editor.level.save(&path) {
+            Ok(()) => locale.format("editor-saved", [("path", display.into())]),
+            Err(error) => locale.format(
+                "editor-save-failed",
+                [
+                    ("path", display.into()),
+                    ("error", error.to_string().into()),
+                ],
+            ),
+        };
+    } else if keyboard_input.just_pressed(KeyCode::KeyO) {
+        // Each press opens the level after the current one, wrapping around at the end.
+        let names = match Level::names() {
+            Ok(names) => names,
+            Err(error) => {
+                editor.status =
+                    locale.format("editor-list-failed", [("error", error.to_string().into())]);
+                return;
+            }
+        };
+        let Some(name) = names
+            .iter()
+            .find(|name| **name > editor.name)
+            .or(names.first())
+            .cloned()
+        else {
+            editor.status = locale.get("editor-no-levels");
+            return;
+        };
+        let path = Level::path(&name);
+        let display = path.display().to_string();
+        editor.status = match Level::load(&path) {
+            Ok(mut level) => {
+                let columns = level.columns;
+                level.rows.resize(MAX_ROWS, vec![None; columns]);
+                editor.level = level;
+                editor.name = name;
+                locale.format("editor-loaded", [("path", display.into())])
+            }
+            Err(error) => locale.format(
+                "editor-load-failed",
+                [
+                    ("path", display.into()),
+                    ("error", error.to_string().into()),
+                ],
+            ),
+        };
+    } else if keyboard_input.just_pressed(KeyCode::KeyN) {
+        editor.level = Level::new(MAX_ROWS, settings.brick_columns);
+        editor.status = locale.format("editor-new", [("columns", settings.brick_columns.into())]);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn sync_grid(
+    mut commands: Commands,
+    editor: Res<Editor>,
+    grid: Option<Res<EditorGrid>>,
+    assets: Res<GameAssets>,
+    editor_assets: Res<EditorAssets>,
+    theme: Res<Theme>,
+    window: Single<&Window>,
+    mut cell_query: Query<(Entity, &EditorCell, &mut MeshMaterial2d<ColorMaterial>)>,
+) {
+    let level = &editor.level;
+    let cell_brick = |row: usize, column: usize| {
+        level.rows[row][column].map(|brick| Brick::new(brick.kind, brick.color))
+    };
+    let cell_material = |row: usize, column: usize| match cell_brick(row, column) {
+        Some(brick) => assets.brick_material(&brick),
+        None => editor_assets.empty_material.clone(),
+    };
+    // Cells always carry their brick symbol so designers can tell the types apart.
+    let spawn_symbol = |cell: &mut EntityCommands, row: usize, column: usize, scale: Vec3| {
+        let Some(brick) = cell_brick(row, column) else {
+            return;
+        };
+        let symbol = brick_symbol(&brick);
+        if !symbol.is_empty() {
+            let color = symbol_color(brick_color(&theme, &brick));
+            cell.with_child(symbol_bundle(symbol, color, scale));
+        }
+    };
+
+    let rebuild = grid
+        .as_ref()
+        .is_none_or(|grid| grid.layout.columns != level.columns || grid.rows != level.rows.len());
+    if rebuild {
+        for (entity, ..) in &cell_query {
+            commands.entity(entity).despawn();
+        }
+
+        let bounds = Rect::from_center_size(Vec2::ZERO, window.size());
+        let layout = ArenaLayout::new(bounds, level.columns);
+        for row in 0..level.rows.len() {
+            for column in 0..level.columns {
+                let scale = Vec3::new(layout.brick_width, BRICK_HEIGHT, 1.0);
+                let mut cell = commands.spawn((
+                    EditorCell { row, column },
+                    DespawnOnExit(GlobalGameState::Editor),
+                    Mesh2d(assets.rectangle.clone()),
+                    MeshMaterial2d(cell_material(row, column)),
+                    Transform {
+                        translation: layout.brick_position(row, column),
+                        scale,
+                        ..default()
+                    },
+                ));
+                spawn_symbol(&mut cell, row, column, scale);
+            }
+        }
+        commands.insert_resource(EditorGrid {
+            layout,
+            rows: level.rows.len(),
+        });
+    } else if editor.is_changed() || theme.is_changed() {
+        let scale = Vec3::new(
+            grid.map_or(0.0, |grid| grid.layout.brick_width),
+            BRICK_HEIGHT,
+            1.0,
+        );
+        for (entity, cell, mut material) in &mut cell_query {
+            material.0 = cell_material(cell.row, cell.column);
+            let mut cell_commands = commands.entity(entity);
+            cell_commands.despawn_children();
+            spawn_symbol(&mut cell_commands, cell.row, cell.column, scale);
+        }
+    }
+}
+
+fn update_editor_hud(
+    editor: Res<Editor>,
+    locale: Res<Locale>,
+    mut hud: Single<&mut Text, With<EditorHud>>,
+) {
+    if !editor.is_changed() && !hud.0.is_empty() {
+        return;
+    }
+
+    let brush = locale.format(
+        "editor-brush",
+        [
+            ("kind", locale.get(editor.brush.kind.message_id()).into()),
+            ("color", ((editor.brush.color + 1) % 10).into()),
+        ],
+    );
+    let name = match &editor.naming {
+        Some(name) => locale.format("editor-naming", [("name", name.clone().into())]),
+        None => locale.format("editor-name", [("name", editor.name.clone().into())]),
+    };
+    hud.0 = format!(
+        "{name}   {brush}   {}\n{}",
+        editor.status,
+        locale.get("editor-help")
+    );
+}
@@ -0,0 +1,149 @@
+use super::{
+    game_over_overlay, run_game_tick, spawn_result_lines, Brick, CollisionEvent, GameOverOverlay,
+    GameState, Paddle, PushBrickRow,
+};
+use crate::level::BrickKind;
+use crate::locale::Locale;
+use crate::stats::Stats;
+use crate::theme::Theme;
+use crate::{GameMode, GameSettings, GlobalGameState};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const ROW_SECONDS: f32 = 10.0;
+const ROW_PADDLE_HITS: u32 = 8;
+
+// A new row arrives every `ROW_SECONDS` of play or every `ROW_PADDLE_HITS` paddle hits,
+// whichever comes first.
+#[derive(Resource)]
+pub(super) struct Endless {
+    rows: u64,
+    paddle_hits: u32,
+    timer: Timer,
+    // The record before this run, so the results can tell whether it was beaten.
+    best: u64,
+}
+
+// What a saved game keeps of an endless run. The record is read again from the statistics.
+#[derive(Serialize, Deserialize)]
+pub(super) struct EndlessProgress {
+    rows: u64,
+    paddle_hits: u32,
+    row_elapsed: f32,
+}
+
+impl Endless {
+    pub(super) fn progress(&self) -> EndlessProgress {
+        EndlessProgress {
+            rows: self.rows,
+            paddle_hits: self.paddle_hits,
+            row_elapsed: self.timer.elapsed_secs(),
+        }
+    }
+
+    pub(super) fn restore(&mut self, progress: &EndlessProgress) {
+        self.rows = progress.rows;
+        self.paddle_hits = progress.paddle_hits;
+        self.timer
+            .set_elapsed(Duration::from_secs_f32(progress.row_elapsed));
+    }
+
+    pub(super) fn hud_text(&self, locale: &Locale, lives: u32) -> String {
+        locale.format(
+            "hud-endless",
+            [
+                ("lives", lives.into()),
+                ("rows", self.rows.into()),
+                ("seconds", self.timer.remaining_secs().ceil().into()),
+                (
+                    "hits",
+                    ROW_PADDLE_HITS.saturating_sub(self.paddle_hits).into(),
+                ),
+            ],
+        )
+    }
+}
+
+pub(super) fn endless_plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(GlobalGameState::Game),
+        start_run.run_if(|settings: Res<GameSettings>| settings.mode == GameMode::Endless),
+    )
+    .add_systems(OnExit(GlobalGameState::Game), |mut commands: Commands| {
+        commands.remove_resource::<Endless>()
+    })
+    .add_systems(
+        OnEnter(GameState::GameOver),
+        show_results
+            .after(game_over_overlay)
+            .run_if(resource_exists::<Endless>),
+    )
+    .add_systems(
+        FixedUpdate,
+        push_rows
+            .after(run_game_tick)
+            .run_if(in_state(GameState::Play).and(resource_exists::<Endless>)),
+    )
+    .add_observer(on_paddle_hit);
+}
+
+pub(super) fn start_run(mut commands: Commands, stats: Res<Stats>) {
+    commands.insert_resource(Endless {
+        rows: 0,
+        paddle_hits: 0,
+        timer: Timer::from_seconds(ROW_SECONDS, TimerMode::Once),
+        best: stats.most_endless_rows,
+    });
+}
+
+fn on_paddle_hit(
+    collision: On<CollisionEvent>,
+    run: Option<ResMut<Endless>>,
+    paddle_query: Query<(), With<Paddle>>,
+) {
+    if let Some(mut run) = run {
+        if paddle_query.contains(collision.entity) {
+            run.paddle_hits += 1;
+        }
+    }
+}
+
+fn push_rows(
+    mut commands: Commands,
+    mut run: ResMut<Endless>,
+    mut stats: ResMut<Stats>,
+    time: Res<Time>,
+    brick_query: Query<&Brick>,
+) {
+    let cleared = brick_query
+        .iter()
+        .all(|brick| brick.kind == BrickKind::Unbreakable);
+    let due = run.timer.tick(time.delta()).is_finished() || run.paddle_hits >= ROW_PADDLE_HITS;
+    if !due && !cleared {
+        return;
+    }
+
+    run.timer.reset();
+    run.paddle_hits = 0;
+    run.rows += 1;
+    stats.most_endless_rows = stats.most_endless_rows.max(run.rows);
+    commands.trigger(PushBrickRow { arena: 0 });
+}
+
+fn show_results(
+    mut commands: Commands,
+    run: Res<Endless>,
+    overlay: Single<Entity, With<GameOverOverlay>>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+) {
+    let mut lines = vec![locale.format("result-rows", [("rows", run.rows.into())])];
+    if run.rows > run.best {
+        lines.push(locale.get("result-new-best"));
+    } else {
+        lines.push(locale.format("result-best-rows", [("rows", run.best.into())]));
+    }
+
+    spawn_result_lines(&mut commands, *overlay, lines, &theme);
+}
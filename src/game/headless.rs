@@ -0,0 +1,74 @@
+use super::{run_game_tick, Brick, GameState, Lives, Scores};
+use crate::level::BrickKind;
+use bevy::prelude::*;
+
+// Present when the game runs without a window for a fixed number of ticks.
+#[derive(Resource)]
+pub(crate) struct HeadlessRun {
+    ticks: u64,
+    remaining: u64,
+}
+
+impl HeadlessRun {
+    pub(crate) fn new(ticks: u64) -> Self {
+        Self {
+            ticks,
+            remaining: ticks,
+        }
+    }
+}
+
+pub(super) fn headless_plugin(app: &mut App) {
+    let headless = resource_exists::<HeadlessRun>;
+    app.add_systems(
+        OnEnter(GameState::Ready),
+        (|mut next_state: ResMut<NextState<GameState>>| next_state.set(GameState::Play))
+            .run_if(headless),
+    )
+    .add_systems(
+        FixedUpdate,
+        count_tick
+            .after(run_game_tick)
+            .run_if(in_state(GameState::Play).and(headless)),
+    )
+    .add_systems(OnEnter(GameState::GameOver), finish.run_if(headless));
+}
+
+fn count_tick(
+    mut run: ResMut<HeadlessRun>,
+    lives: Res<Lives>,
+    scores: Res<Scores>,
+    brick_query: Query<&Brick>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    run.remaining = run.remaining.saturating_sub(1);
+    if run.remaining == 0 {
+        report(&run, &lives, &scores, &brick_query);
+        exit.write(AppExit::Success);
+    }
+}
+
+fn finish(
+    run: Res<HeadlessRun>,
+    lives: Res<Lives>,
+    scores: Res<Scores>,
+    brick_query: Query<&Brick>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    report(&run, &lives, &scores, &brick_query);
+    exit.write(AppExit::Success);
+}
+
+fn report(run: &HeadlessRun, lives: &Lives, scores: &Scores, brick_query: &Query<&Brick>) {
+    let bricks = brick_query
+        .iter()
+        .filter(|brick| brick.kind != BrickKind::Unbreakable)
+        .count();
+    info!(
+        "headless run stopped after {} of {} ticks: lives {:?}, scores {:?}, {bricks} bricks left",
+        run.ticks - run.remaining,
+        run.ticks,
+        lives.0,
+        scores.0,
+    );
+}
@@ -0,0 +1,230 @@
+use super::{ArenaLayout, Brick, Velocity, BRICK_AREA_GUTTER, BRICK_GAP};
+use crate::level::{Level, RowMotion};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+
+const SWAY_PERIOD: f32 = 4.0;
+const LOOP_PERIOD: f32 = 2.0;
+const SPIN_PERIOD: f32 = 6.0;
+// Looping bricks move sideways in step with the rest of their row, so only the arena edge
+// limits how far they go. They dip less than the gap so they never reach the row below.
+const LOOP_REACH: f32 = BRICK_AREA_GUTTER;
+const LOOP_DEPTH: f32 = BRICK_GAP * 0.8;
+
+// Positions are worked out from the time since the brick was spawned rather than
+// accumulated, so a restored snapshot or saved game puts every brick back on its path.
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
+pub(super) struct BrickMotion {
+    anchor: Vec2,
+    path: MotionPath,
+    elapsed: f32,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+enum MotionPath {
+    // Horizontal offset `middle + reach * sin(angle + phase)`, which starts at zero.
+    Sway { middle: f32, reach: f32, phase: f32 },
+    Loop,
+    // Turns like a card around the vertical line at `center`, narrowing to nothing edge-on.
+    Spin { center: f32, width: f32 },
+}
+
+impl BrickMotion {
+    // Sets up the motion for the brick at `row`, `column` of `level`, if its row moves.
+    pub(super) fn for_level_brick(
+        level: &Level,
+        layout: &ArenaLayout,
+        row: usize,
+        column: usize,
+    ) -> Option<Self> {
+        let cells = &level.rows[row];
+        let position = |column: usize| layout.brick_position(row, column).truncate();
+        let anchor = position(column);
+        let path = match level.motions.get(&row)? {
+            RowMotion::Sway => {
+                let step = layout.brick_width + BRICK_GAP;
+                let first = cells.iter().position(Option::is_some)?;
+                let last = cells.iter().rposition(Option::is_some)?;
+                let left = first as f32 * step;
+                let right = (cells.len() - 1 - last) as f32 * step;
+                let (middle, reach) = ((right - left) / 2.0, (right + left) / 2.0);
+                let phase = if reach > 0.0 {
+                    (-middle / reach).asin()
+                } else {
+                    0.0
+                };
+                MotionPath::Sway {
+                    middle,
+                    reach,
+                    phase,
+                }
+            }
+            RowMotion::Loop => MotionPath::Loop,
+            // Columns turn in pairs, swapping cells every half turn, so neither brick leaves
+            // the two cells. A brick without its partner turns on the spot.
+            RowMotion::Spin => {
+                let center = match cells.get(column ^ 1) {
+                    Some(Some(_)) => (anchor.x + position(column ^ 1).x) / 2.0,
+                    _ => anchor.x,
+                };
+                MotionPath::Spin {
+                    center,
+                    width: layout.brick_width,
+                }
+            }
+        };
+        Some(Self {
+            anchor,
+            path,
+            elapsed: 0.0,
+        })
+    }
+
+    // Where the brick is now, which is its anchor when it has only just been spawned.
+    pub(super) fn position(&self) -> Vec2 {
+        match self.path {
+            MotionPath::Sway {
+                middle,
+                reach,
+                phase,
+            } => {
+                let angle = self.elapsed * TAU / SWAY_PERIOD;
+                self.anchor + Vec2::X * (middle + reach * (angle + phase).sin())
+            }
+            MotionPath::Loop => {
+                let angle = self.elapsed * TAU / LOOP_PERIOD;
+                self.anchor
+                    + Vec2::new(
+                        LOOP_REACH * angle.sin(),
+                        LOOP_DEPTH / 2.0 * (angle.cos() - 1.0),
+                    )
+            }
+            MotionPath::Spin { center, .. } => {
+                let angle = self.elapsed * TAU / SPIN_PERIOD;
+                Vec2::new(
+                    center + (self.anchor.x - center) * angle.cos(),
+                    self.anchor.y,
+                )
+            }
+        }
+    }
+
+    // The brick's width now, for paths that squeeze it rather than keep the cell width.
+    pub(super) fn width(&self) -> Option<f32> {
+        match self.path {
+            MotionPath::Spin { width, .. } => {
+                let angle = self.elapsed * TAU / SPIN_PERIOD;
+                Some(width * angle.cos().abs())
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn shift(&mut self, offset: Vec2) {
+        self.anchor += offset;
+        if let MotionPath::Spin { center, .. } = &mut self.path {
+            *center += offset.x;
+        }
+    }
+}
+
+// Runs in `GameTick` before collisions, leaving each brick's velocity for the ball to pick up.
+pub(super) fn move_bricks(
+    mut brick_query: Query<(&mut BrickMotion, &mut Transform, &mut Velocity), With<Brick>>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+    for (mut motion, mut transform, mut velocity) in &mut brick_query {
+        motion.elapsed += delta;
+        let position = motion.position();
+        velocity.0 = (position - transform.translation.truncate()) / delta;
+        transform.translation = position.extend(transform.translation.z);
+        if let Some(width) = motion.width() {
+            transform.scale.x = width;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::BRICK_HEIGHT;
+    use crate::level::{BrickKind, LevelBrick};
+
+    fn moving_level(rows: usize, columns: usize) -> Level {
+        let brick = LevelBrick {
+            kind: BrickKind::Normal,
+            color: 0,
+        };
+        let mut level = Level::new(rows, columns);
+        for (row, cells) in level.rows.iter_mut().enumerate() {
+            cells.fill(Some(brick));
+            // Gaps in some rows give sways room and leave spinning bricks without a partner.
+            if row % 3 == 1 {
+                cells[columns / 2] = None;
+            }
+            // Every fourth row stays put, so moving rows pass bricks that don't move.
+            let motion = match row % 4 {
+                1 => RowMotion::Sway,
+                2 => RowMotion::Loop,
+                3 => RowMotion::Spin,
+                _ => continue,
+            };
+            level.motions.insert(row, motion);
+        }
+        level
+    }
+
+    #[test]
+    fn moving_bricks_stay_inside_the_arena_and_apart() {
+        let bounds = Rect::from_center_size(Vec2::ZERO, Vec2::new(1280.0, 720.0));
+        for columns in [1, 5, 10, 20] {
+            let layout = ArenaLayout::new(bounds, columns);
+            for rows in [4, 10] {
+                let level = moving_level(rows, columns);
+                let mut bricks: Vec<_> = level
+                    .bricks()
+                    .map(|(row, column, _)| {
+                        (
+                            layout.brick_position(row, column).truncate(),
+                            BrickMotion::for_level_brick(&level, &layout, row, column),
+                        )
+                    })
+                    .collect();
+                for _ in 0..=(SPIN_PERIOD * 20.0) as usize {
+                    let boxes: Vec<Rect> = bricks
+                        .iter()
+                        .map(|(position, motion)| {
+                            let (position, width) = match motion {
+                                Some(motion) => (
+                                    motion.position(),
+                                    motion.width().unwrap_or(layout.brick_width),
+                                ),
+                                None => (*position, layout.brick_width),
+                            };
+                            Rect::from_center_size(position, Vec2::new(width, BRICK_HEIGHT))
+                        })
+                        .collect();
+                    for (index, a) in boxes.iter().enumerate() {
+                        assert!(a.min.x >= bounds.min.x && a.max.x <= bounds.max.x);
+                        assert!(a.max.y <= bounds.max.y);
+                        for b in &boxes[index + 1..] {
+                            let overlap = a.intersect(*b);
+                            assert!(
+                                overlap.width() <= 0.0 || overlap.height() <= 0.0,
+                                "{columns} columns: {a:?} overlaps {b:?}"
+                            );
+                        }
+                    }
+                    for motion in bricks.iter_mut().filter_map(|(_, motion)| motion.as_mut()) {
+                        motion.elapsed += 0.05;
+                    }
+                }
+            }
+        }
+    }
+}
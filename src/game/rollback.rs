@@ -0,0 +1,521 @@
+use super::motion::BrickMotion;
+use super::{
+    brick_bundle, on_brick_collision, player_direction, resume_state, Arena, Ball, Brick, BrickId,
+    GameAssets, GameRng, GameState, GameTick, Garbage, LastHit, Lives, NextBrickId, Paddle,
+    PaddleInputs, PendingOutcome, Scores, StallTimer, Velocity, PLAYER_KEYS,
+};
+use crate::net::NetSession;
+use crate::{GameSettings, GlobalGameState};
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use std::collections::{BTreeMap, VecDeque};
+
+const INPUT_DELAY: u32 = 2;
+const MAX_ROLLBACK: u32 = 30;
+
+#[derive(Resource)]
+struct RollbackState {
+    frame: u32,
+    confirmed: u32,
+    local_input: i8,
+    local_inputs: BTreeMap<u32, i8>,
+    remote_inputs: BTreeMap<u32, i8>,
+    predicted: BTreeMap<u32, i8>,
+    snapshots: VecDeque<Snapshot>,
+    // The frame that produced the pending outcome, if any.
+    outcome_frame: Option<u32>,
+    // Whether the local player has pressed start.
+    ready: bool,
+}
+
+impl Default for RollbackState {
+    fn default() -> Self {
+        Self {
+            frame: 0,
+            confirmed: 0,
+            local_input: 0,
+            local_inputs: (0..INPUT_DELAY).map(|frame| (frame, 0)).collect(),
+            remote_inputs: BTreeMap::new(),
+            predicted: BTreeMap::new(),
+            snapshots: VecDeque::new(),
+            outcome_frame: None,
+            ready: false,
+        }
+    }
+}
+
+impl RollbackState {
+    fn remote_input(&self, frame: u32) -> i8 {
+        // Predict that the remote player keeps doing whatever they last did.
+        self.remote_inputs
+            .range(..=frame)
+            .next_back()
+            .map_or(0, |(_, input)| *input)
+    }
+
+    // Records the remote player's inputs and returns the earliest frame that was simulated
+    // with a wrong guess, if any.
+    fn receive_remote(&mut self, received: Vec<(u32, i8)>) -> Option<u32> {
+        let mut rollback_to: Option<u32> = None;
+        for (frame, input) in received {
+            if frame < self.confirmed || self.remote_inputs.contains_key(&frame) {
+                continue;
+            }
+            self.remote_inputs.insert(frame, input);
+            if frame < self.frame && self.predicted.get(&frame) != Some(&input) {
+                rollback_to = Some(rollback_to.map_or(frame, |earliest| earliest.min(frame)));
+            }
+        }
+        while self.remote_inputs.contains_key(&self.confirmed) {
+            self.confirmed += 1;
+        }
+        rollback_to
+    }
+
+    // Queues the local input for a new frame, unless that would run further ahead of the
+    // remote player than we are able to roll back.
+    fn advance(&mut self) -> bool {
+        if self.frame >= self.confirmed + MAX_ROLLBACK {
+            return false;
+        }
+        self.local_inputs
+            .insert(self.frame + INPUT_DELAY, self.local_input);
+        true
+    }
+
+    // The local and remote inputs for the next frame, remembering the remote guess.
+    fn frame_inputs(&mut self) -> (i8, i8) {
+        let local_input = self.local_inputs.get(&self.frame).copied().unwrap_or(0);
+        let remote_input = self.remote_input(self.frame);
+        self.predicted.insert(self.frame, remote_input);
+        (local_input, remote_input)
+    }
+
+    fn send(&self, session: &mut NetSession) {
+        if let Some((&last_frame, _)) = self.local_inputs.last_key_value() {
+            let inputs: Vec<i8> = self.local_inputs.values().copied().collect();
+            session.send_inputs(last_frame, &inputs);
+        }
+    }
+
+    fn prune(&mut self) {
+        let horizon = self.confirmed.min(self.frame);
+        while self
+            .snapshots
+            .front()
+            .is_some_and(|snapshot| snapshot.frame < horizon)
+        {
+            self.snapshots.pop_front();
+        }
+        let keep_from = self.frame.saturating_sub(MAX_ROLLBACK * 2);
+        self.local_inputs = self.local_inputs.split_off(&keep_from);
+        self.predicted = self.predicted.split_off(&horizon);
+        self.remote_inputs = self.remote_inputs.split_off(&horizon.saturating_sub(1));
+    }
+}
+
+// Everything the `GameTick` simulation reads or writes, captured before a frame runs.
+struct Snapshot {
+    frame: u32,
+    rng: GameRng,
+    next_brick_id: NextBrickId,
+    lives: Lives,
+    scores: Scores,
+    garbage: Garbage,
+    pending_outcome: PendingOutcome,
+    paddles: Vec<(Entity, Transform, Velocity)>,
+    balls: Vec<(Entity, Transform, Velocity, LastHit, StallTimer)>,
+    bricks: Vec<(BrickId, Brick, Arena, Transform, Option<BrickMotion>)>,
+}
+
+pub(super) fn rollback_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GlobalGameState::Game), |mut commands: Commands| {
+        commands.insert_resource(RollbackState::default())
+    })
+    .add_systems(
+        Update,
+        start_online_game.run_if(in_state(GameState::Ready).and(resource_exists::<NetSession>)),
+    )
+    .add_systems(
+        FixedUpdate,
+        (read_online_input, rollback_tick)
+            .chain()
+            .run_if(in_state(GameState::Play).and(resource_exists::<NetSession>)),
+    );
+}
+
+// Both players have to press start before either leaves `Ready`, so neither peer runs
+// frames before the other has joined in.
+fn start_online_game(
+    mut state: ResMut<RollbackState>,
+    mut session: ResMut<NetSession>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    settings: Res<GameSettings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        state.ready = true;
+    }
+    if !state.ready {
+        return;
+    }
+    session.send_ready();
+    // A peer that started first is already sending its inputs for the opening frames.
+    let received = session.receive_inputs();
+    state.receive_remote(received);
+    if session.peer_ready() {
+        next_state.set(resume_state(&settings));
+    }
+}
+
+fn read_online_input(
+    mut state: ResMut<RollbackState>,
+    session: Res<NetSession>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+) {
+    let direction = player_direction(
+        &keyboard_input,
+        gamepads.iter().next(),
+        PLAYER_KEYS[session.local_player()],
+    );
+    state.local_input = (direction * i8::MAX as f32).round() as i8;
+}
+
+fn rollback_tick(world: &mut World) {
+    let Some(mut session) = world.remove_resource::<NetSession>() else {
+        return;
+    };
+    let mut state = world.resource_mut::<RollbackState>();
+    let mut state = std::mem::take(&mut *state);
+    let local_player = session.local_player();
+
+    let rollback_to = state.receive_remote(session.receive_inputs());
+    let rollback_index = rollback_to.and_then(|frame| {
+        state
+            .snapshots
+            .iter()
+            .position(|snapshot| snapshot.frame == frame)
+    });
+    if let Some(index) = rollback_index {
+        let frame = state.snapshots[index].frame;
+        load_snapshot(world, &state.snapshots[index]);
+        state.snapshots.truncate(index);
+        if world.resource::<PendingOutcome>().0.is_none() {
+            state.outcome_frame = None;
+        }
+        let current = state.frame;
+        state.frame = frame;
+        while state.frame < current {
+            simulate_frame(world, &mut state, local_player);
+        }
+    }
+
+    if state.advance() {
+        simulate_frame(world, &mut state, local_player);
+    }
+    state.send(&mut session);
+    state.prune();
+
+    // The game only ends once both players' inputs up to the deciding frame are known, so
+    // both peers end it the same way.
+    if state
+        .outcome_frame
+        .is_some_and(|frame| frame < state.confirmed)
+    {
+        if let Some(outcome) = world.resource_mut::<PendingOutcome>().0.take() {
+            world.insert_resource(outcome);
+            world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::GameOver);
+        }
+    }
+
+    *world.resource_mut::<RollbackState>() = state;
+    world.insert_resource(session);
+}
+
+fn simulate_frame(world: &mut World, state: &mut RollbackState, local_player: usize) {
+    let frame = state.frame;
+    let (local_input, remote_input) = state.frame_inputs();
+    state.snapshots.push_back(save_snapshot(world, frame));
+
+    let mut inputs = [0.0; 2];
+    inputs[local_player] = local_input as f32 / i8::MAX as f32;
+    inputs[1 - local_player] = remote_input as f32 / i8::MAX as f32;
+    world.insert_resource(PaddleInputs(inputs));
+    world.run_schedule(GameTick);
+
+    if state.outcome_frame.is_none() && world.resource::<PendingOutcome>().0.is_some() {
+        state.outcome_frame = Some(frame);
+    }
+    state.frame += 1;
+}
+
+fn save_snapshot(world: &mut World, frame: u32) -> Snapshot {
+    let paddles = world
+        .query_filtered::<(Entity, &Transform, &Velocity), With<Paddle>>()
+        .iter(world)
+        .map(|(entity, transform, velocity)| (entity, *transform, *velocity))
+        .collect();
+    let balls = world
+        .query_filtered::<(Entity, &Transform, &Velocity, &LastHit, &StallTimer), With<Ball>>()
+        .iter(world)
+        .map(|(entity, transform, velocity, last_hit, stall_timer)| {
+            (
+                entity,
+                *transform,
+                *velocity,
+                *last_hit,
+                stall_timer.clone(),
+            )
+        })
+        .collect();
+    let bricks = world
+        .query::<(&BrickId, &Brick, &Arena, &Transform, Option<&BrickMotion>)>()
+        .iter(world)
+        .map(|(id, brick, arena, transform, motion)| {
+            (*id, *brick, *arena, *transform, motion.copied())
+        })
+        .collect();
+
+    Snapshot {
+        frame,
+        rng: world.resource::<GameRng>().clone(),
+        next_brick_id: *world.resource::<NextBrickId>(),
+        lives: world.resource::<Lives>().clone(),
+        scores: world.resource::<Scores>().clone(),
+        garbage: world.resource::<Garbage>().clone(),
+        pending_outcome: *world.resource::<PendingOutcome>(),
+        paddles,
+        balls,
+        bricks,
+    }
+}
+
+fn load_snapshot(world: &mut World, snapshot: &Snapshot) {
+    world.insert_resource(snapshot.rng.clone());
+    world.insert_resource(snapshot.next_brick_id);
+    world.insert_resource(snapshot.lives.clone());
+    world.insert_resource(snapshot.scores.clone());
+    world.insert_resource(snapshot.garbage.clone());
+    world.insert_resource(snapshot.pending_outcome);
+
+    for (entity, transform, velocity) in &snapshot.paddles {
+        if let Ok(mut paddle) = world.get_entity_mut(*entity) {
+            paddle.insert((*transform, *velocity));
+        }
+    }
+    for (entity, transform, velocity, last_hit, stall_timer) in &snapshot.balls {
+        if let Ok(mut ball) = world.get_entity_mut(*entity) {
+            ball.insert((*transform, *velocity, *last_hit, stall_timer.clone()));
+        }
+    }
+
+    let existing: HashMap<BrickId, Entity> = world
+        .query_filtered::<(Entity, &BrickId), With<Brick>>()
+        .iter(world)
+        .map(|(entity, id)| (*id, entity))
+        .collect();
+    let restored: HashSet<BrickId> = snapshot.bricks.iter().map(|(id, ..)| *id).collect();
+    for (id, entity) in &existing {
+        if !restored.contains(id) {
+            world.despawn(*entity);
+        }
+    }
+
+    for (id, brick, arena, transform, motion) in &snapshot.bricks {
+        let assets = world.resource::<GameAssets>();
+        let mut entity = match existing.get(id) {
+            Some(entity) => {
+                let material = assets.brick_material(brick);
+                let mut entity = world.entity_mut(*entity);
+                entity.insert((*brick, *transform));
+                if let Some(mut mesh_material) = entity.get_mut::<MeshMaterial2d<ColorMaterial>>() {
+                    mesh_material.0 = material;
+                }
+                entity
+            }
+            None => {
+                let bundle = brick_bundle(*id, *brick, *arena, assets, *transform);
+                let mut entity = world.spawn(bundle);
+                entity.observe(on_brick_collision);
+                entity
+            }
+        };
+        if let Some(motion) = motion {
+            entity.insert((*motion, Velocity(Vec2::ZERO)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{game_setup, game_tick_plugin};
+    use crate::net::NetConfig;
+    use crate::theme::{Theme, BRICK_COLOR_COUNT};
+    use crate::GameMode;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::net::{SocketAddr, UdpSocket};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    const FRAMES: u32 = 600;
+
+    // Changes every few frames, so some of the remote guesses turn out wrong.
+    fn input(player: usize, frame: u32) -> i8 {
+        (((frame / 7 + player as u32 * 3) % 5) as i8 - 2) * 60
+    }
+
+    // What the peers have to agree on once a frame is confirmed. Entities are left out, as
+    // each world numbers its own.
+    #[derive(PartialEq, Debug)]
+    struct Digest {
+        lives: Vec<u32>,
+        scores: [u32; 2],
+        paddles: Vec<[u32; 2]>,
+        balls: Vec<[u32; 4]>,
+        bricks: Vec<(u32, u32, [u32; 2])>,
+    }
+
+    fn bits(vector: Vec2) -> [u32; 2] {
+        vector.to_array().map(f32::to_bits)
+    }
+
+    fn digest(snapshot: &Snapshot) -> Digest {
+        let mut paddles: Vec<_> = snapshot
+            .paddles
+            .iter()
+            .map(|(_, transform, _)| bits(transform.translation.truncate()))
+            .collect();
+        let mut balls: Vec<_> = snapshot
+            .balls
+            .iter()
+            .map(|(_, transform, velocity, ..)| {
+                let [x, y] = bits(transform.translation.truncate());
+                let [dx, dy] = bits(velocity.0);
+                [x, y, dx, dy]
+            })
+            .collect();
+        let mut bricks: Vec<_> = snapshot
+            .bricks
+            .iter()
+            .map(|(id, brick, _, transform, _)| {
+                (id.0, brick.hits, bits(transform.translation.truncate()))
+            })
+            .collect();
+        paddles.sort();
+        balls.sort();
+        bricks.sort();
+        Digest {
+            lives: snapshot.lives.0.clone(),
+            scores: snapshot.scores.0,
+            paddles,
+            balls,
+            bricks,
+        }
+    }
+
+    fn placeholder_assets() -> GameAssets {
+        GameAssets {
+            rectangle: default(),
+            circle: default(),
+            paddle_materials: vec![default(); 2],
+            ball_material: default(),
+            divider_material: default(),
+            brick_materials: vec![default(); BRICK_COLOR_COUNT],
+            cracked_materials: vec![default(); BRICK_COLOR_COUNT],
+            unbreakable_material: default(),
+        }
+    }
+
+    // A headless online versus game with nothing but the simulation, which the test drives
+    // through `rollback_tick` the way `FixedUpdate` would.
+    fn peer(bind: SocketAddr, peer: SocketAddr, local_player: usize) -> App {
+        let config = NetConfig {
+            bind,
+            peer,
+            local_player,
+            seed: 7,
+            packet_loss: 0.3,
+            delay: Duration::from_millis(5),
+        };
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f64(1.0 / 120.0));
+
+        let mut app = App::new();
+        app.add_plugins(game_tick_plugin)
+            .insert_resource(time)
+            .insert_resource(GameSettings {
+                mode: GameMode::Versus,
+                online: true,
+                ..default()
+            })
+            .insert_resource(Theme::default())
+            .insert_resource(placeholder_assets())
+            .insert_resource(RollbackState::default())
+            .insert_resource(NetSession::bind(&config).expect("loopback socket"))
+            .insert_resource(config)
+            .init_resource::<NextState<GameState>>();
+        app.world_mut().spawn(Window::default());
+        app.world_mut()
+            .run_system_once(game_setup)
+            .expect("game setup runs");
+        app
+    }
+
+    fn free_address() -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").expect("loopback socket");
+        socket.local_addr().expect("bound address")
+    }
+
+    #[test]
+    fn peers_agree_on_every_confirmed_frame() {
+        let (first, second) = (free_address(), free_address());
+        let mut peers = [peer(first, second, 0), peer(second, first, 1)];
+        let mut digests = [BTreeMap::new(), BTreeMap::new()];
+
+        let confirmed = |app: &App| app.world().resource::<RollbackState>().confirmed;
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while peers.iter().any(|app| confirmed(app) < FRAMES) {
+            assert!(Instant::now() < deadline, "peers stopped making progress");
+            for (app, digests) in peers.iter_mut().zip(&mut digests) {
+                let world = app.world_mut();
+                let local_player = world.resource::<NetSession>().local_player();
+                let mut state = world.resource_mut::<RollbackState>();
+                state.local_input = input(local_player, state.frame);
+                rollback_tick(world);
+
+                // Whatever comes before the first unconfirmed frame only follows from known
+                // inputs, so both peers must have reached exactly the same state there.
+                let state = world.resource::<RollbackState>();
+                let (frame, confirmed) = (state.frame, state.confirmed);
+                if frame <= confirmed {
+                    digests.insert(frame, digest(&save_snapshot(world, frame)));
+                } else if let Some(snapshot) = state
+                    .snapshots
+                    .iter()
+                    .find(|snapshot| snapshot.frame == confirmed)
+                {
+                    digests.insert(confirmed, digest(snapshot));
+                }
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let common: Vec<_> = digests[0]
+            .keys()
+            .filter(|frame| digests[1].contains_key(frame))
+            .collect();
+        assert!(
+            common.len() > FRAMES as usize / 4,
+            "too few frames to compare"
+        );
+        for frame in common {
+            assert_eq!(digests[0][frame], digests[1][frame], "frame {frame}");
+        }
+        // Make sure the balls got as far as the bricks, or collisions went untested.
+        assert!(digests[0].values().any(|digest| digest.scores != [0, 0]));
+    }
+}
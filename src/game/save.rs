@@ -0,0 +1,459 @@
+use super::endless::{self, Endless, EndlessProgress};
+use super::motion::BrickMotion;
+use super::time_attack::{self, TimeAttack};
+use super::{
+    ball_bundle, brick_bundle, game_setup, offline, on_ball_collision, on_brick_collision, Arena,
+    Arenas, Ball, Brick, BrickId, GameAssets, GameRng, GameState, Garbage, HeadlessRun, LastHit,
+    Lives, NextBrickId, Paddle, Scores, Velocity, BALL_RADIUS, BRICK_HEIGHT,
+};
+use crate::level::{BrickKind, Level};
+use crate::{save_dir, GameMode, GameSettings, GlobalGameState};
+use bevy::prelude::*;
+use rand::rngs::Xoshiro256PlusPlus;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+// Writes the game in progress to disk.
+#[derive(Event)]
+pub(crate) struct SaveGame;
+
+// Restores the saved game and switches to it.
+#[derive(Event)]
+pub(crate) struct ContinueGame;
+
+#[derive(Serialize, Deserialize)]
+struct SavedGame {
+    mode: GameMode,
+    brick_rows: usize,
+    brick_columns: usize,
+    level: Option<String>,
+    // Saves from before time attack and endless runs could be saved have neither.
+    #[serde(default)]
+    time_limit_minutes: usize,
+    #[serde(default)]
+    time_attack: Option<TimeAttack>,
+    #[serde(default)]
+    endless: Option<EndlessProgress>,
+    rng: Xoshiro256PlusPlus,
+    next_brick_id: u32,
+    lives: Vec<u32>,
+    scores: [u32; 2],
+    garbage: [u32; 2],
+    paddles: Vec<SavedPaddle>,
+    balls: Vec<SavedBall>,
+    bricks: Vec<SavedBrick>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedPaddle {
+    player: usize,
+    x: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedBall {
+    arena: usize,
+    position: (f32, f32),
+    velocity: (f32, f32),
+    last_hit: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedBrick {
+    id: u32,
+    arena: usize,
+    kind: BrickKind,
+    color: usize,
+    hits: u32,
+    position: (f32, f32),
+    // Saves from before moving bricks have no motion.
+    #[serde(default)]
+    motion: Option<BrickMotion>,
+}
+
+#[derive(Resource)]
+struct ResumeGame(SavedGame);
+
+// The settings a continued game replaced, put back when it ends so the menu shows what the
+// player had chosen.
+#[derive(Resource)]
+struct MenuSettings {
+    mode: GameMode,
+    brick_rows: usize,
+    brick_columns: usize,
+    time_limit_minutes: usize,
+    level: Option<Level>,
+}
+
+fn save_path() -> PathBuf {
+    save_dir().join("game.ron")
+}
+
+pub(crate) fn has_saved_game() -> bool {
+    save_path().is_file()
+}
+
+fn remove_saved_game() {
+    if let Err(error) = fs::remove_file(save_path()) {
+        if error.kind() != io::ErrorKind::NotFound {
+            warn!("failed to remove saved game: {error}");
+        }
+    }
+}
+
+pub(super) fn save_plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(GlobalGameState::Game),
+        resume_game
+            .after(game_setup)
+            .after(time_attack::start_run)
+            .after(endless::start_run)
+            .run_if(resource_exists::<ResumeGame>),
+    )
+    .add_systems(
+        OnExit(GlobalGameState::Game),
+        restore_menu_settings.run_if(resource_exists::<MenuSettings>),
+    )
+    // A finished game has nothing left to continue.
+    .add_systems(
+        OnEnter(GameState::GameOver),
+        remove_saved_game.run_if(offline.and(not(resource_exists::<HeadlessRun>))),
+    )
+    .add_systems(
+        Last,
+        save_on_exit
+            .run_if(in_state(GlobalGameState::Game).and(not(in_state(GameState::GameOver))))
+            .run_if(offline.and(not(resource_exists::<HeadlessRun>))),
+    )
+    .add_observer(on_save_game)
+    .add_observer(on_continue_game);
+}
+
+fn save_on_exit(mut commands: Commands, mut exit: MessageReader<AppExit>) {
+    if exit.read().next().is_some() {
+        commands.trigger(SaveGame);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn on_save_game(
+    _save: On<SaveGame>,
+    settings: Res<GameSettings>,
+    rng: Res<GameRng>,
+    next_brick_id: Res<NextBrickId>,
+    lives: Res<Lives>,
+    scores: Res<Scores>,
+    garbage: Res<Garbage>,
+    time_attack: Option<Res<TimeAttack>>,
+    endless: Option<Res<Endless>>,
+    paddle_query: Query<(&Paddle, &Transform)>,
+    ball_query: Query<(&Arena, &Transform, &Velocity, &LastHit), With<Ball>>,
+    brick_query: Query<(&BrickId, &Arena, &Brick, &Transform, Option<&BrickMotion>)>,
+) {
+    let saved = SavedGame {
+        mode: settings.mode,
+        brick_rows: settings.brick_rows,
+        brick_columns: settings.brick_columns,
+        level: settings.level.as_ref().map(Level::to_string),
+        time_limit_minutes: settings.time_limit_minutes,
+        time_attack: time_attack.map(|run| run.clone()),
+        endless: endless.map(|run| run.progress()),
+        rng: rng.0.clone(),
+        next_brick_id: next_brick_id.0,
+        lives: lives.0.clone(),
+        scores: scores.0,
+        garbage: garbage.0,
+        paddles: paddle_query
+            .iter()
+            .map(|(paddle, transform)| SavedPaddle {
+                player: paddle.player,
+                x: transform.translation.x,
+            })
+            .collect(),
+        balls: ball_query
+            .iter()
+            .map(|(arena, transform, velocity, last_hit)| SavedBall {
+                arena: arena.0,
+                position: transform.translation.truncate().into(),
+                velocity: velocity.0.into(),
+                last_hit: last_hit.0,
+            })
+            .collect(),
+        bricks: brick_query
+            .iter()
+            .map(|(id, arena, brick, transform, motion)| SavedBrick {
+                id: id.0,
+                arena: arena.0,
+                kind: brick.kind,
+                color: brick.color,
+                hits: brick.hits,
+                position: transform.translation.truncate().into(),
+                motion: motion.copied(),
+            })
+            .collect(),
+    };
+
+    let result = ron::ser::to_string_pretty(&saved, ron::ser::PrettyConfig::default())
+        .map_err(io::Error::other)
+        .and_then(|source| {
+            fs::create_dir_all(save_dir())?;
+            fs::write(save_path(), source)
+        });
+    if let Err(error) = result {
+        warn!("failed to save game: {error}");
+    }
+}
+
+fn on_continue_game(
+    _continue: On<ContinueGame>,
+    mut commands: Commands,
+    mut settings: ResMut<GameSettings>,
+    mut game_state: ResMut<NextState<GlobalGameState>>,
+) {
+    let saved = fs::read_to_string(save_path()).and_then(|source| {
+        ron::from_str::<SavedGame>(&source)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    });
+    let saved = match saved {
+        Ok(saved) => saved,
+        Err(error) => {
+            warn!("failed to load saved game: {error}");
+            return;
+        }
+    };
+    let level = match saved.level.as_deref().map(str::parse::<Level>).transpose() {
+        Ok(level) => level,
+        Err(error) => {
+            warn!("failed to load saved game: {error}");
+            return;
+        }
+    };
+
+    // The arenas are laid out from the settings, so they must match the saved game until it
+    // ends.
+    commands.insert_resource(MenuSettings {
+        mode: settings.mode,
+        brick_rows: settings.brick_rows,
+        brick_columns: settings.brick_columns,
+        time_limit_minutes: settings.time_limit_minutes,
+        level: settings.level.take(),
+    });
+    settings.mode = saved.mode;
+    settings.online = false;
+    settings.brick_rows = saved.brick_rows;
+    settings.brick_columns = saved.brick_columns;
+    settings.time_limit_minutes = saved.time_limit_minutes;
+    settings.level = level;
+    commands.insert_resource(ResumeGame(saved));
+    game_state.set(GlobalGameState::Game);
+    // A save is continued once; quitting again writes a new one.
+    remove_saved_game();
+}
+
+fn restore_menu_settings(
+    mut commands: Commands,
+    menu_settings: Res<MenuSettings>,
+    mut settings: ResMut<GameSettings>,
+) {
+    settings.mode = menu_settings.mode;
+    settings.brick_rows = menu_settings.brick_rows;
+    settings.brick_columns = menu_settings.brick_columns;
+    settings.time_limit_minutes = menu_settings.time_limit_minutes;
+    settings.level = menu_settings.level.clone();
+    commands.remove_resource::<MenuSettings>();
+}
+
+// Runs after `game_setup` has built a fresh game, and after time attack and endless runs
+// have started, and replaces their state with the saved one.
+#[allow(clippy::too_many_arguments)]
+fn resume_game(
+    mut commands: Commands,
+    resume: Res<ResumeGame>,
+    endless: Option<ResMut<Endless>>,
+    arenas: Res<Arenas>,
+    assets: Res<GameAssets>,
+    mut paddle_query: Query<(&Paddle, &mut Transform)>,
+    ball_query: Query<Entity, With<Ball>>,
+    brick_query: Query<Entity, With<Brick>>,
+) {
+    let saved = &resume.0;
+    commands.insert_resource(GameRng(saved.rng.clone()));
+    commands.insert_resource(NextBrickId(saved.next_brick_id));
+    commands.insert_resource(Lives(saved.lives.clone()));
+    commands.insert_resource(Scores(saved.scores));
+    commands.insert_resource(Garbage(saved.garbage));
+    if let Some(run) = &saved.time_attack {
+        commands.insert_resource(run.clone());
+    }
+    if let (Some(mut run), Some(progress)) = (endless, &saved.endless) {
+        run.restore(progress);
+    }
+
+    for (paddle, mut transform) in &mut paddle_query {
+        if let Some(saved) = saved
+            .paddles
+            .iter()
+            .find(|saved| saved.player == paddle.player)
+        {
+            transform.translation.x = saved.x;
+        }
+    }
+
+    for entity in &ball_query {
+        commands.entity(entity).despawn();
+    }
+    for ball in &saved.balls {
+        let Some(layout) = arenas.get(ball.arena) else {
+            continue;
+        };
+        commands
+            .spawn(ball_bundle(ball.arena, layout, &assets))
+            .insert((
+                Velocity(ball.velocity.into()),
+                LastHit(ball.last_hit),
+                Transform {
+                    translation: Vec2::from(ball.position).extend(0.0),
+                    scale: Vec2::splat(BALL_RADIUS * 2.0).extend(1.0),
+                    ..default()
+                },
+            ))
+            .observe(on_ball_collision);
+    }
+
+    for entity in &brick_query {
+        commands.entity(entity).despawn();
+    }
+    for brick in &saved.bricks {
+        let Some(layout) = arenas.get(brick.arena) else {
+            continue;
+        };
+        let transform = Transform {
+            translation: Vec2::from(brick.position).extend(0.0),
+            scale: Vec3::new(
+                brick
+                    .motion
+                    .and_then(|motion| motion.width())
+                    .unwrap_or(layout.brick_width),
+                BRICK_HEIGHT,
+                1.0,
+            ),
+            ..default()
+        };
+        let state = Brick {
+            kind: brick.kind,
+            color: brick.color,
+            hits: brick.hits,
+        };
+        let mut entity = commands.spawn(brick_bundle(
+            BrickId(brick.id),
+            state,
+            Arena(brick.arena),
+            &assets,
+            transform,
+        ));
+        entity.observe(on_brick_collision);
+        if let Some(motion) = brick.motion {
+            entity.insert((motion, Velocity(Vec2::ZERO)));
+        }
+    }
+
+    commands.remove_resource::<ResumeGame>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::ArenaLayout;
+    use crate::level::RowMotion;
+    use rand::SeedableRng;
+
+    fn saved_game() -> SavedGame {
+        let mut level: Level = "N0 T1 U2 @spin\n.. N3 ..".parse().unwrap();
+        level.motions.insert(1, RowMotion::Loop);
+        let bounds = Rect::from_center_size(Vec2::ZERO, Vec2::new(1280.0, 720.0));
+        let layout = ArenaLayout::new(bounds, level.columns);
+        SavedGame {
+            mode: GameMode::TimeAttack,
+            brick_rows: 2,
+            brick_columns: 3,
+            level: Some(level.to_string()),
+            time_limit_minutes: 5,
+            time_attack: Some(ron::from_str("(stage: 1, elapsed: 42.5, splits: [30.25])").unwrap()),
+            endless: Some(ron::from_str("(rows: 7, paddle_hits: 3, row_elapsed: 4.5)").unwrap()),
+            rng: Xoshiro256PlusPlus::seed_from_u64(7),
+            next_brick_id: 4,
+            lives: vec![2],
+            scores: [150, 0],
+            garbage: [0, 0],
+            paddles: vec![SavedPaddle {
+                player: 0,
+                x: -12.0,
+            }],
+            balls: vec![SavedBall {
+                arena: 0,
+                position: (3.0, -40.0),
+                velocity: (250.0, 250.0),
+                last_hit: 0,
+            }],
+            bricks: level
+                .bricks()
+                .enumerate()
+                .map(|(id, (row, column, brick))| SavedBrick {
+                    id: id as u32,
+                    arena: 0,
+                    kind: brick.kind,
+                    color: brick.color,
+                    hits: brick.kind.hits(),
+                    position: layout.brick_position(row, column).truncate().into(),
+                    motion: BrickMotion::for_level_brick(&level, &layout, row, column),
+                })
+                .collect(),
+        }
+    }
+
+    fn to_ron(saved: &SavedGame) -> String {
+        ron::ser::to_string_pretty(saved, ron::ser::PrettyConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn round_trips_through_ron() {
+        let source = to_ron(&saved_game());
+        let loaded: SavedGame = ron::from_str(&source).unwrap();
+        assert_eq!(to_ron(&loaded), source);
+        assert_eq!(loaded.rng, Xoshiro256PlusPlus::seed_from_u64(7));
+        assert_eq!(
+            loaded
+                .level
+                .unwrap()
+                .parse::<Level>()
+                .unwrap()
+                .motions
+                .len(),
+            2
+        );
+        assert!(loaded.time_attack.is_some() && loaded.endless.is_some());
+        assert!(loaded.bricks.iter().all(|brick| brick.motion.is_some()));
+    }
+
+    #[test]
+    fn loads_saves_from_before_runs_were_saved() {
+        let mut saved = saved_game();
+        saved.mode = GameMode::Solo;
+        saved.time_attack = None;
+        saved.endless = None;
+        let source = to_ron(&saved)
+            .lines()
+            .filter(|line| {
+                !["time_limit_minutes", "time_attack", "endless"]
+                    .iter()
+                    .any(|field| line.trim_start().starts_with(field))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let loaded: SavedGame = ron::from_str(&source).unwrap();
+        assert_eq!(loaded.time_limit_minutes, 0);
+        assert!(loaded.time_attack.is_none() && loaded.endless.is_none());
+    }
+}
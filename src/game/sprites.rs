@@ -0,0 +1,125 @@
+use super::{Ball, Brick, Paddle};
+use crate::asset_dir;
+use crate::level::BrickKind;
+use crate::loading::Preload;
+use crate::theme::BRICK_COLOR_COUNT;
+use bevy::prelude::*;
+use std::path::Path;
+
+const SPRITE_DIR: &str = "sprites";
+const BRICK_SPRITE_SIZE: UVec2 = UVec2::new(64, 32);
+
+// Also catches whatever was spawned before the pack finished loading.
+type WithoutSprite = (Or<(With<Paddle>, With<Ball>, With<Brick>)>, Without<Sprite>);
+
+// The pack lives in assets/sprites:
+//   bricks.png  a grid of 64x32 cells, one column per brick colour. Row 0 holds intact
+//               bricks, row 1 cracked bricks and the first cell of row 2 the unbreakable brick.
+//   paddle.png  stretched over the paddle.
+//   ball.png    stretched over the ball.
+#[derive(Resource)]
+struct SpritePack {
+    bricks: Handle<Image>,
+    brick_layout: Handle<TextureAtlasLayout>,
+    paddle: Handle<Image>,
+    ball: Handle<Image>,
+}
+
+impl SpritePack {
+    fn images(&self) -> [UntypedHandle; 3] {
+        [
+            self.bricks.clone().untyped(),
+            self.paddle.clone().untyped(),
+            self.ball.clone().untyped(),
+        ]
+    }
+}
+
+pub(super) fn sprites_plugin(app: &mut App) {
+    app.add_systems(Startup, load_sprite_pack).add_systems(
+        PostUpdate,
+        (use_sprites, update_brick_sprites).run_if(sprite_pack_loaded),
+    );
+}
+
+fn load_sprite_pack(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut preload: ResMut<Preload>,
+) {
+    // The pack is optional; without it everything keeps its flat-colour mesh.
+    if !asset_dir().join(SPRITE_DIR).is_dir() {
+        return;
+    }
+
+    let path = Path::new(SPRITE_DIR);
+    let pack = SpritePack {
+        bricks: asset_server.load(path.join("bricks.png")),
+        brick_layout: layouts.add(TextureAtlasLayout::from_grid(
+            BRICK_SPRITE_SIZE,
+            BRICK_COLOR_COUNT as u32,
+            3,
+            None,
+            None,
+        )),
+        paddle: asset_server.load(path.join("paddle.png")),
+        ball: asset_server.load(path.join("ball.png")),
+    };
+    for image in pack.images() {
+        preload.add(image);
+    }
+    commands.insert_resource(pack);
+}
+
+fn sprite_pack_loaded(pack: Option<Res<SpritePack>>, asset_server: Res<AssetServer>) -> bool {
+    pack.is_some_and(|pack| {
+        pack.images()
+            .iter()
+            .all(|image| asset_server.is_loaded_with_dependencies(image))
+    })
+}
+
+fn brick_sprite_index(brick: &Brick) -> usize {
+    match brick.kind {
+        BrickKind::Unbreakable => BRICK_COLOR_COUNT * 2,
+        _ if brick.hits < brick.kind.hits() => BRICK_COLOR_COUNT + brick.color,
+        _ => brick.color,
+    }
+}
+
+// Swaps the mesh of paddles, balls and bricks for a sprite. The transform
+// scale stays the collider size, so sprites are drawn at a unit size.
+fn use_sprites(
+    mut commands: Commands,
+    pack: Res<SpritePack>,
+    query: Query<(Entity, Option<&Brick>, Has<Paddle>), WithoutSprite>,
+) {
+    for (entity, brick, is_paddle) in &query {
+        let mut sprite = match brick {
+            Some(brick) => Sprite::from_atlas_image(
+                pack.bricks.clone(),
+                TextureAtlas {
+                    layout: pack.brick_layout.clone(),
+                    index: brick_sprite_index(brick),
+                },
+            ),
+            None if is_paddle => Sprite::from_image(pack.paddle.clone()),
+            None => Sprite::from_image(pack.ball.clone()),
+        };
+        sprite.custom_size = Some(Vec2::ONE);
+
+        commands
+            .entity(entity)
+            .remove::<(Mesh2d, MeshMaterial2d<ColorMaterial>)>()
+            .insert(sprite);
+    }
+}
+
+fn update_brick_sprites(mut query: Query<(&Brick, &mut Sprite), Changed<Brick>>) {
+    for (brick, mut sprite) in &mut query {
+        if let Some(atlas) = &mut sprite.texture_atlas {
+            atlas.index = brick_sprite_index(brick);
+        }
+    }
+}
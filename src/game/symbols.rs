@@ -0,0 +1,84 @@
+use super::{Brick, BRICK_HEIGHT};
+use crate::level::BrickKind;
+use crate::theme::Theme;
+use crate::GameSettings;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub(super) struct BrickSymbol;
+
+pub(super) fn symbols_plugin(app: &mut App) {
+    app.add_systems(PostUpdate, update_brick_symbols);
+}
+
+// Marks show the hits a brick has left, so brick types don't rely on colour alone.
+pub(super) fn brick_symbol(brick: &Brick) -> &'static str {
+    match brick.kind {
+        BrickKind::Normal => "",
+        BrickKind::Tough if brick.hits > 1 => "II",
+        BrickKind::Tough => "I",
+        BrickKind::Unbreakable => "X",
+    }
+}
+
+pub(super) fn brick_color(theme: &Theme, brick: &Brick) -> Color {
+    match brick.kind {
+        BrickKind::Unbreakable => theme.unbreakable,
+        _ => theme.bricks[brick.color],
+    }
+}
+
+pub(super) fn symbol_color(background: Color) -> Color {
+    if background.luminance() > 0.5 {
+        Color::BLACK
+    } else {
+        Color::WHITE
+    }
+}
+
+// Symbols are children of the scaled brick, so they undo the scale to keep their shape.
+pub(super) fn symbol_bundle(symbol: &str, color: Color, brick_scale: Vec3) -> impl Bundle {
+    (
+        BrickSymbol,
+        Text2d::new(symbol),
+        TextFont {
+            font_size: BRICK_HEIGHT * 0.8,
+            ..default()
+        },
+        TextColor(color),
+        Transform {
+            translation: Vec3::Z,
+            scale: brick_scale.recip(),
+            ..default()
+        },
+    )
+}
+
+fn update_brick_symbols(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    brick_query: Query<(Entity, Ref<Brick>, &Transform, Option<&Children>)>,
+    symbol_query: Query<(), With<BrickSymbol>>,
+) {
+    let refresh_all = settings.is_changed() || theme.is_changed();
+    for (entity, brick, transform, children) in &brick_query {
+        if !refresh_all && !brick.is_changed() {
+            continue;
+        }
+
+        for child in children.into_iter().flatten() {
+            if symbol_query.contains(*child) {
+                commands.entity(*child).despawn();
+            }
+        }
+
+        let symbol = brick_symbol(&brick);
+        if settings.brick_symbols && !symbol.is_empty() {
+            let color = symbol_color(brick_color(&theme, &brick));
+            commands
+                .entity(entity)
+                .with_child(symbol_bundle(symbol, color, transform.scale));
+        }
+    }
+}
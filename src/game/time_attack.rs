@@ -0,0 +1,338 @@
+use super::{
+    game_over_overlay, run_game_tick, spawn_bricks, spawn_result_lines, Arenas, Ball, Brick,
+    GameAssets, GameOutcome, GameOverOverlay, GameRng, GameState, HeadlessRun, NextBrickId,
+    Velocity, BALL_SPEED,
+};
+use crate::level::MAX_ROWS;
+use crate::locale::Locale;
+use crate::theme::Theme;
+use crate::{save_dir, GameMode, GameSettings, GlobalGameState};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const STAGES: usize = 3;
+const RECORD_RUNS: usize = 5;
+
+// The run in progress; the clock only moves while the game is in play.
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub(super) struct TimeAttack {
+    stage: usize,
+    elapsed: f32,
+    // Seconds each cleared stage took.
+    splits: Vec<f32>,
+}
+
+impl TimeAttack {
+    pub(super) fn hud_text(&self, locale: &Locale, settings: &GameSettings, lives: u32) -> String {
+        let clock = match time_limit(settings) {
+            Some(limit) => limit - self.elapsed,
+            None => self.elapsed,
+        };
+        let splits = if self.splits.is_empty() {
+            "-".to_string()
+        } else {
+            self.splits
+                .iter()
+                .map(|split| format_time(*split))
+                .collect::<Vec<_>>()
+                .join("  ")
+        };
+        locale.format(
+            "hud-time-attack",
+            [
+                ("lives", lives.into()),
+                ("stage", (self.stage + 1).into()),
+                ("stages", STAGES.into()),
+                ("time", format_time(clock).into()),
+                ("splits", splits.into()),
+            ],
+        )
+    }
+}
+
+// Fired by `check_outcome` instead of ending the game when a time attack stage is cleared.
+#[derive(Event)]
+pub(super) struct StageCleared;
+
+// The fastest finished runs, stored one per line as the seconds each stage took.
+#[derive(Resource, Default)]
+struct TimeAttackRecords(Vec<Vec<f32>>);
+
+impl TimeAttackRecords {
+    fn path() -> PathBuf {
+        save_dir().join("time_attack.txt")
+    }
+
+    fn load() -> io::Result<Self> {
+        let source = match fs::read_to_string(Self::path()) {
+            Ok(source) => source,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(error),
+        };
+
+        let mut runs = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let splits = line
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<Vec<f32>, _>>()
+                .map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {}: invalid split time", index + 1),
+                    )
+                })?;
+            runs.push(splits);
+        }
+        runs.sort_by(|a, b| total(a).total_cmp(&total(b)));
+        runs.truncate(RECORD_RUNS);
+        Ok(Self(runs))
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let source: String = self
+            .0
+            .iter()
+            .map(|splits| {
+                let line: Vec<_> = splits.iter().map(f32::to_string).collect();
+                line.join(" ") + "\n"
+            })
+            .collect();
+        fs::create_dir_all(save_dir())?;
+        fs::write(Self::path(), source)
+    }
+
+    // Returns the zero-based rank of the run, or `None` if it is too slow to be kept.
+    fn insert(&mut self, splits: Vec<f32>) -> Option<usize> {
+        let rank = self.0.partition_point(|run| total(run) <= total(&splits));
+        if rank >= RECORD_RUNS {
+            return None;
+        }
+        self.0.insert(rank, splits);
+        self.0.truncate(RECORD_RUNS);
+        Some(rank)
+    }
+}
+
+pub(super) fn time_attack_plugin(app: &mut App) {
+    let records = TimeAttackRecords::load().unwrap_or_else(|error| {
+        warn!("failed to read time attack records: {error}");
+        TimeAttackRecords::default()
+    });
+    app.insert_resource(records)
+        .add_systems(
+            OnEnter(GlobalGameState::Game),
+            start_run.run_if(|settings: Res<GameSettings>| settings.mode == GameMode::TimeAttack),
+        )
+        .add_systems(OnExit(GlobalGameState::Game), |mut commands: Commands| {
+            commands.remove_resource::<TimeAttack>()
+        })
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            show_results
+                .after(game_over_overlay)
+                .run_if(resource_exists::<TimeAttack>),
+        )
+        .add_systems(
+            FixedUpdate,
+            tick_clock
+                .after(run_game_tick)
+                .run_if(in_state(GameState::Play).and(resource_exists::<TimeAttack>)),
+        )
+        .add_observer(on_stage_cleared);
+}
+
+fn total(splits: &[f32]) -> f32 {
+    splits.iter().sum()
+}
+
+fn time_limit(settings: &GameSettings) -> Option<f32> {
+    (settings.time_limit_minutes > 0).then_some(settings.time_limit_minutes as f32 * 60.0)
+}
+
+// Each stage adds a row to the random layout; custom levels are replayed as they are.
+fn stage_rows(settings: &GameSettings, stage: usize) -> usize {
+    (settings.brick_rows + stage).min(MAX_ROWS)
+}
+
+fn format_time(seconds: f32) -> String {
+    let hundredths = (seconds.max(0.0) * 100.0).round() as u64;
+    let (minutes, seconds, hundredths) =
+        (hundredths / 6000, hundredths / 100 % 60, hundredths % 100);
+    format!("{minutes}:{seconds:02}.{hundredths:02}")
+}
+
+fn format_difference(seconds: f32) -> String {
+    let sign = if seconds < 0.0 { '-' } else { '+' };
+    format!("{sign}{}", format_time(seconds.abs()))
+}
+
+pub(super) fn start_run(mut commands: Commands) {
+    commands.insert_resource(TimeAttack::default());
+}
+
+fn tick_clock(
+    mut commands: Commands,
+    mut run: ResMut<TimeAttack>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    run.elapsed += time.delta_secs();
+    if let Some(limit) = time_limit(&settings) {
+        if run.elapsed >= limit {
+            run.elapsed = limit;
+            commands.insert_resource(GameOutcome::Lost);
+            next_state.set(GameState::GameOver);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn on_stage_cleared(
+    _cleared: On<StageCleared>,
+    mut commands: Commands,
+    mut run: ResMut<TimeAttack>,
+    settings: Res<GameSettings>,
+    assets: Res<GameAssets>,
+    arenas: Res<Arenas>,
+    mut rng: ResMut<GameRng>,
+    mut next_brick_id: ResMut<NextBrickId>,
+    brick_query: Query<Entity, With<Brick>>,
+    mut ball_query: Query<(&mut Transform, &mut Velocity), With<Ball>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let split = run.elapsed - total(&run.splits);
+    run.splits.push(split);
+    run.stage += 1;
+    if run.stage == STAGES {
+        commands.insert_resource(GameOutcome::Cleared);
+        next_state.set(GameState::GameOver);
+        return;
+    }
+
+    // Unbreakable bricks outlive a cleared stage and would overlap the next one.
+    for brick in &brick_query {
+        commands.entity(brick).despawn();
+    }
+    let layout = &arenas[0];
+    for (mut transform, mut velocity) in &mut ball_query {
+        transform.translation = layout.ball_start();
+        velocity.0 = Vec2::new(BALL_SPEED, BALL_SPEED);
+    }
+    spawn_bricks(
+        &mut commands,
+        &assets,
+        &mut rng,
+        &mut next_brick_id,
+        layout,
+        0,
+        settings.level.as_ref(),
+        stage_rows(&settings, run.stage),
+    );
+    next_state.set(GameState::Ready);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_results(
+    mut commands: Commands,
+    run: Res<TimeAttack>,
+    mut records: ResMut<TimeAttackRecords>,
+    outcome: Res<GameOutcome>,
+    overlay: Single<Entity, With<GameOverOverlay>>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+    headless: Option<Res<HeadlessRun>>,
+) {
+    let best = records.0.first().cloned();
+    let mut lines = vec![locale.format("result-time", [("time", format_time(run.elapsed).into())])];
+    for (stage, split) in run.splits.iter().enumerate() {
+        let stage_number = (stage + 1).into();
+        let time = format_time(*split).into();
+        lines.push(match best.as_ref().and_then(|best| best.get(stage)) {
+            Some(best_split) => locale.format(
+                "result-split-versus",
+                [
+                    ("stage", stage_number),
+                    ("time", time),
+                    ("difference", format_difference(split - best_split).into()),
+                ],
+            ),
+            None => locale.format("result-split", [("stage", stage_number), ("time", time)]),
+        });
+    }
+
+    if matches!(*outcome, GameOutcome::Cleared) {
+        let rank = records.insert(run.splits.clone());
+        if rank.is_some() && headless.is_none() {
+            if let Err(error) = records.save() {
+                warn!("failed to save time attack records: {error}");
+            }
+        }
+        match (rank, &best) {
+            (Some(0), _) => lines.push(locale.get("result-new-best")),
+            (_, Some(best)) => lines.push(locale.format(
+                "result-best",
+                [
+                    ("time", format_time(total(best)).into()),
+                    (
+                        "difference",
+                        format_difference(run.elapsed - total(best)).into(),
+                    ),
+                ],
+            )),
+            _ => {}
+        }
+        lines.push(match rank {
+            Some(rank) => locale.format(
+                "result-rank",
+                [
+                    ("rank", (rank + 1).into()),
+                    ("count", records.0.len().into()),
+                ],
+            ),
+            None => locale.format("result-unranked", [("count", RECORD_RUNS.into())]),
+        });
+    }
+
+    spawn_result_lines(&mut commands, *overlay, lines, &theme);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(totals: &[f32]) -> TimeAttackRecords {
+        TimeAttackRecords(totals.iter().map(|total| vec![*total]).collect())
+    }
+
+    fn totals(records: &TimeAttackRecords) -> Vec<f32> {
+        records.0.iter().map(|run| total(run)).collect()
+    }
+
+    #[test]
+    fn ranks_runs_by_total_time() {
+        let mut records = records(&[30.0, 40.0, 50.0]);
+        assert_eq!(records.insert(vec![10.0, 15.0, 10.0]), Some(1));
+        assert_eq!(records.insert(vec![20.0]), Some(0));
+        // A tie goes after the run that set the time first.
+        assert_eq!(records.insert(vec![20.0, 20.0]), Some(4));
+        assert_eq!(totals(&records), [20.0, 30.0, 35.0, 40.0, 40.0]);
+        assert_eq!(records.0[3], [40.0]);
+    }
+
+    #[test]
+    fn drops_runs_too_slow_to_keep() {
+        let mut records = records(&[10.0, 20.0, 30.0, 40.0, 50.0]);
+        assert_eq!(records.insert(vec![60.0]), None);
+        assert_eq!(records.insert(vec![50.0]), None);
+        assert_eq!(records.insert(vec![15.0]), Some(1));
+        assert_eq!(totals(&records), [10.0, 15.0, 20.0, 30.0, 40.0]);
+    }
+}
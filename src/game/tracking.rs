@@ -0,0 +1,158 @@
+use super::{
+    run_game_tick, Ball, BallLost, BrickBroken, CollisionEvent, GameOutcome, GameState,
+    HeadlessRun, Lives, Paddle, Velocity, BALL_MAX_SPEED, STARTING_LIVES,
+};
+use crate::achievements::{Achievement, Unlock};
+use crate::stats::Stats;
+use crate::{GameSettings, GlobalGameState};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+const COMBO_BRICKS: u32 = 10;
+
+// Rollback re-simulates online ticks and would count them twice, so only offline games
+// are tracked. Headless runs are simulations rather than the player's own games.
+#[derive(Resource, Default)]
+struct Tracking {
+    // Bricks each ball has broken since it last touched a paddle.
+    combos: HashMap<Entity, u32>,
+    // Paddle hits each ball has had since it was last lost.
+    rallies: HashMap<Entity, u64>,
+    // Seconds of play in the current game.
+    game_time: f32,
+    // Games started since the player last came from the menu or the editor.
+    attempts: u32,
+}
+
+pub(super) fn tracking_plugin(app: &mut App) {
+    app.init_resource::<Tracking>()
+        .add_systems(OnEnter(GlobalGameState::Menu), reset_attempts)
+        .add_systems(OnEnter(GlobalGameState::Editor), reset_attempts)
+        .add_systems(
+            OnEnter(GlobalGameState::Game),
+            start_attempt.run_if(tracked),
+        )
+        .add_systems(OnExit(GlobalGameState::Game), save_stats.run_if(tracked))
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            (check_game_over, save_stats).chain().run_if(tracked),
+        )
+        .add_systems(
+            FixedUpdate,
+            (count_play_time, check_top_speed)
+                .after(run_game_tick)
+                .run_if(in_state(GameState::Play).and(tracked)),
+        )
+        .add_observer(on_brick_broken)
+        .add_observer(on_paddle_hit)
+        .add_observer(on_ball_lost);
+}
+
+fn tracked(settings: Res<GameSettings>, headless: Option<Res<HeadlessRun>>) -> bool {
+    !settings.online && headless.is_none()
+}
+
+fn reset_attempts(mut tracking: ResMut<Tracking>) {
+    tracking.attempts = 0;
+}
+
+fn start_attempt(mut tracking: ResMut<Tracking>, mut stats: ResMut<Stats>) {
+    tracking.attempts += 1;
+    tracking.combos.clear();
+    tracking.rallies.clear();
+    tracking.game_time = 0.0;
+    stats.games_played += 1;
+}
+
+fn save_stats(stats: Res<Stats>) {
+    if let Err(error) = stats.save() {
+        warn!("failed to save statistics: {error}");
+    }
+}
+
+fn count_play_time(mut tracking: ResMut<Tracking>, mut stats: ResMut<Stats>, time: Res<Time>) {
+    tracking.game_time += time.delta_secs();
+    stats.play_time += time.delta_secs_f64();
+}
+
+fn check_game_over(
+    mut commands: Commands,
+    tracking: Res<Tracking>,
+    mut stats: ResMut<Stats>,
+    outcome: Res<GameOutcome>,
+    lives: Res<Lives>,
+) {
+    if !matches!(*outcome, GameOutcome::Cleared) {
+        return;
+    }
+    if stats
+        .fastest_clear
+        .is_none_or(|fastest| tracking.game_time < fastest)
+    {
+        stats.fastest_clear = Some(tracking.game_time);
+    }
+    if tracking.attempts == 1 {
+        commands.trigger(Unlock(Achievement::FirstTry));
+    }
+    if lives.iter().all(|lives| *lives == STARTING_LIVES) {
+        commands.trigger(Unlock(Achievement::Flawless));
+    }
+}
+
+fn check_top_speed(mut commands: Commands, ball_query: Query<&Velocity, With<Ball>>) {
+    if ball_query
+        .iter()
+        .any(|velocity| velocity.length() >= BALL_MAX_SPEED - 1.0)
+    {
+        commands.trigger(Unlock(Achievement::TopSpeed));
+    }
+}
+
+fn on_brick_broken(
+    broken: On<BrickBroken>,
+    mut commands: Commands,
+    mut tracking: ResMut<Tracking>,
+    mut stats: ResMut<Stats>,
+    settings: Res<GameSettings>,
+) {
+    if settings.online {
+        return;
+    }
+    stats.bricks_broken += 1;
+    let combo = tracking.combos.entry(broken.ball).or_default();
+    *combo += 1;
+    if *combo == COMBO_BRICKS {
+        commands.trigger(Unlock(Achievement::Combo));
+    }
+}
+
+fn on_paddle_hit(
+    collision: On<CollisionEvent>,
+    mut tracking: ResMut<Tracking>,
+    mut stats: ResMut<Stats>,
+    settings: Res<GameSettings>,
+    paddle_query: Query<(), With<Paddle>>,
+) {
+    if settings.online || !paddle_query.contains(collision.entity) {
+        return;
+    }
+    tracking.combos.remove(&collision.ball);
+    let rally = tracking.rallies.entry(collision.ball).or_default();
+    *rally += 1;
+    stats.paddle_hits += 1;
+    stats.longest_rally = stats.longest_rally.max(*rally);
+}
+
+fn on_ball_lost(
+    lost: On<BallLost>,
+    mut tracking: ResMut<Tracking>,
+    mut stats: ResMut<Stats>,
+    settings: Res<GameSettings>,
+) {
+    if settings.online {
+        return;
+    }
+    tracking.combos.remove(&lost.ball);
+    tracking.rallies.remove(&lost.ball);
+    stats.balls_lost += 1;
+}
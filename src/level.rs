@@ -0,0 +1,369 @@
+use super::asset_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const LEVEL_DIR: &str = "levels";
+const LEVEL_EXTENSION: &str = "level";
+// The most the arena has room for. More columns leave no width for the bricks in half a
+// versus window, and more rows leave the ball too little room above the paddle.
+pub(crate) const MAX_ROWS: usize = 10;
+pub(crate) const MAX_COLUMNS: usize = 20;
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, Serialize, Deserialize)]
+pub(crate) enum BrickKind {
+    #[default]
+    Normal,
+    Tough,
+    Unbreakable,
+}
+
+impl BrickKind {
+    pub(crate) const ALL: [BrickKind; 3] =
+        [BrickKind::Normal, BrickKind::Tough, BrickKind::Unbreakable];
+
+    // Unbreakable bricks never run out of hits, so they report zero.
+    pub(crate) fn hits(self) -> u32 {
+        match self {
+            BrickKind::Normal => 1,
+            BrickKind::Tough => 2,
+            BrickKind::Unbreakable => 0,
+        }
+    }
+
+    pub(crate) fn message_id(self) -> &'static str {
+        match self {
+            BrickKind::Normal => "brick-normal",
+            BrickKind::Tough => "brick-tough",
+            BrickKind::Unbreakable => "brick-unbreakable",
+        }
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            BrickKind::Normal => 'N',
+            BrickKind::Tough => 'T',
+            BrickKind::Unbreakable => 'U',
+        }
+    }
+
+    fn from_symbol(symbol: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.symbol() == symbol)
+    }
+}
+
+// How the bricks of a row move once the game is running.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub(crate) enum RowMotion {
+    // The row slides back and forth across the free space beside it.
+    Sway,
+    // Every brick circles its own spot.
+    Loop,
+    // Neighbouring bricks flip over in pairs, trading places every half turn.
+    Spin,
+}
+
+impl RowMotion {
+    pub(crate) const ALL: [RowMotion; 3] = [RowMotion::Sway, RowMotion::Loop, RowMotion::Spin];
+
+    pub(crate) fn message_id(self) -> &'static str {
+        match self {
+            RowMotion::Sway => "motion-sway",
+            RowMotion::Loop => "motion-loop",
+            RowMotion::Spin => "motion-spin",
+        }
+    }
+
+    fn tag(self) -> &'static str {
+        match self {
+            RowMotion::Sway => "sway",
+            RowMotion::Loop => "loop",
+            RowMotion::Spin => "spin",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|motion| motion.tag() == tag)
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub(crate) struct LevelBrick {
+    pub kind: BrickKind,
+    pub color: usize,
+}
+
+// A level is a grid of cells, one line per row. Each cell is a brick kind symbol
+// followed by a colour digit (e.g. `N3`, `T0`, `U9`), or `..` when empty.
+// A row may end with a motion tag (`@sway`, `@loop` or `@spin`).
+// Lines starting with `#` are comments.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Level {
+    pub columns: usize,
+    pub rows: Vec<Vec<Option<LevelBrick>>>,
+    pub motions: BTreeMap<usize, RowMotion>,
+}
+
+impl Level {
+    pub(crate) fn new(rows: usize, columns: usize) -> Self {
+        Self {
+            columns,
+            rows: vec![vec![None; columns]; rows],
+            motions: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn path(name: &str) -> PathBuf {
+        asset_dir()
+            .join(LEVEL_DIR)
+            .join(name)
+            .with_extension(LEVEL_EXTENSION)
+    }
+
+    // The names of the levels in the level directory, in alphabetical order.
+    pub(crate) fn names() -> io::Result<Vec<String>> {
+        let mut names = Vec::new();
+        let entries = match fs::read_dir(asset_dir().join(LEVEL_DIR)) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(names),
+            Err(error) => return Err(error),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == LEVEL_EXTENSION)
+            {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    pub(crate) fn load(path: &Path) -> io::Result<Self> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+    }
+
+    pub(crate) fn bricks(&self) -> impl Iterator<Item = (usize, usize, LevelBrick)> + '_ {
+        self.rows.iter().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .filter_map(move |(column, cell)| cell.map(|brick| (row, column, brick)))
+        })
+    }
+}
+
+impl std::str::FromStr for Level {
+    type Err = io::Error;
+
+    fn from_str(source: &str) -> io::Result<Self> {
+        let invalid = |line: usize, message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {line}: {message}"),
+            )
+        };
+
+        let mut rows = Vec::new();
+        let mut motions = BTreeMap::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut tokens: Vec<&str> = line.split_whitespace().collect();
+            if let Some(tag) = tokens.last().and_then(|token| token.strip_prefix('@')) {
+                let motion = RowMotion::from_tag(tag)
+                    .ok_or_else(|| invalid(index + 1, format!("unknown motion `@{tag}`")))?;
+                motions.insert(rows.len(), motion);
+                tokens.pop();
+            }
+
+            let mut cells = Vec::new();
+            for cell in tokens {
+                if cell == ".." {
+                    cells.push(None);
+                    continue;
+                }
+                if cell.starts_with('@') {
+                    return Err(invalid(
+                        index + 1,
+                        format!("motion `{cell}` must be the last token of its row"),
+                    ));
+                }
+                let mut chars = cell.chars();
+                let kind = chars.next().and_then(BrickKind::from_symbol);
+                let color = chars.next().and_then(|digit| digit.to_digit(10));
+                match (kind, color, chars.next()) {
+                    (Some(kind), Some(color), None) => cells.push(Some(LevelBrick {
+                        kind,
+                        color: color as usize,
+                    })),
+                    _ => return Err(invalid(index + 1, format!("invalid cell `{cell}`"))),
+                }
+            }
+            if cells.len() > MAX_COLUMNS {
+                return Err(invalid(
+                    index + 1,
+                    format!(
+                        "expected at most {MAX_COLUMNS} cells, found {}",
+                        cells.len()
+                    ),
+                ));
+            }
+            if rows.len() == MAX_ROWS {
+                return Err(invalid(
+                    index + 1,
+                    format!("a level has at most {MAX_ROWS} rows"),
+                ));
+            }
+            rows.push((index + 1, cells));
+        }
+
+        let columns = rows.first().map_or(0, |(_, cells)| cells.len());
+        if columns == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "level has no rows",
+            ));
+        }
+        if let Some((line, cells)) = rows.iter().find(|(_, cells)| cells.len() != columns) {
+            return Err(invalid(
+                *line,
+                format!("expected {columns} cells, found {}", cells.len()),
+            ));
+        }
+
+        Ok(Self {
+            columns,
+            rows: rows.into_iter().map(|(_, cells)| cells).collect(),
+            motions,
+        })
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "# N = normal, T = tough, U = unbreakable, digit = colour, .. = empty"
+        )?;
+        for (row, cells) in self.rows.iter().enumerate() {
+            let mut cells: Vec<String> = cells
+                .iter()
+                .map(|cell| match cell {
+                    Some(brick) => format!("{}{}", brick.kind.symbol(), brick.color),
+                    None => "..".to_string(),
+                })
+                .collect();
+            if let Some(motion) = self.motions.get(&row) {
+                cells.push(format!("@{}", motion.tag()));
+            }
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+# A comment
+N0 T1 ..
+
+.. U9 N3 @sway
+N2 N2 N2 @spin
+";
+
+    fn error(source: &str) -> String {
+        source.parse::<Level>().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parses_cells_and_motions() {
+        let level: Level = SOURCE.parse().unwrap();
+        assert_eq!(level.columns, 3);
+        assert_eq!(level.rows.len(), 3);
+        assert_eq!(
+            level.rows[0],
+            [
+                Some(LevelBrick {
+                    kind: BrickKind::Normal,
+                    color: 0
+                }),
+                Some(LevelBrick {
+                    kind: BrickKind::Tough,
+                    color: 1
+                }),
+                None,
+            ]
+        );
+        assert_eq!(level.rows[1][1].unwrap().kind, BrickKind::Unbreakable);
+        assert_eq!(
+            level.motions,
+            BTreeMap::from([(1, RowMotion::Sway), (2, RowMotion::Spin)])
+        );
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let level: Level = SOURCE.parse().unwrap();
+        let reparsed: Level = level.to_string().parse().unwrap();
+        assert_eq!(reparsed, level);
+    }
+
+    #[test]
+    fn motion_tags_must_end_the_row() {
+        assert_eq!(
+            error("N0 N0\nN0 @loop N0"),
+            "line 2: motion `@loop` must be the last token of its row"
+        );
+        assert_eq!(
+            error("N0 N0 @sway @spin"),
+            "line 1: motion `@sway` must be the last token of its row"
+        );
+        assert_eq!(error("N0 N0 @twirl"), "line 1: unknown motion `@twirl`");
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_row() {
+        assert_eq!(error("N0 N0\n\nN0 X1"), "line 3: invalid cell `X1`");
+        assert_eq!(
+            error("N0 N0\n# note\nN0"),
+            "line 3: expected 2 cells, found 1"
+        );
+        assert_eq!(error("@spin"), "level has no rows");
+        assert_eq!(error("# only a comment"), "level has no rows");
+    }
+
+    #[test]
+    fn rejects_levels_too_big_for_the_arena() {
+        let row = |columns: usize| vec!["N0"; columns].join(" ") + "\n";
+        assert_eq!(
+            error(&(row(2) + &row(MAX_COLUMNS + 1))),
+            "line 2: expected at most 20 cells, found 21"
+        );
+        assert!(row(MAX_COLUMNS).repeat(MAX_ROWS).parse::<Level>().is_ok());
+        assert_eq!(
+            error(&row(3).repeat(MAX_ROWS + 1)),
+            "line 11: a level has at most 10 rows"
+        );
+    }
+}
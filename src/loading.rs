@@ -0,0 +1,184 @@
+use super::level::Level;
+use super::locale::Locale;
+use super::splash::any_button_pressed;
+use super::theme::{Theme, ThemeColor};
+use super::GlobalGameState;
+use bevy::asset::RecursiveDependencyLoadState;
+use bevy::prelude::*;
+
+// Handles that must finish loading before the menu opens. Plugins add to it at startup.
+// Only what goes through the `AssetServer` is tracked, which is the splash logo and the
+// sprite pack; level files are plain text read from disk when needed, but are checked here
+// so a broken one is reported before anyone picks it.
+#[derive(Resource, Default)]
+pub(crate) struct Preload {
+    pending: Vec<UntypedHandle>,
+    total: usize,
+    // Paths of the assets that failed to load.
+    failed: Vec<String>,
+}
+
+impl Preload {
+    pub(crate) fn add(&mut self, handle: impl Into<UntypedHandle>) {
+        self.pending.push(handle.into());
+        self.total += 1;
+    }
+
+    fn progress(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        1.0 - self.pending.len() as f32 / self.total as f32
+    }
+}
+
+#[derive(Component)]
+struct ProgressBar;
+
+#[derive(Component)]
+struct FailureText;
+
+pub fn loading_plugin(app: &mut App) {
+    app.init_resource::<Preload>()
+        .add_systems(
+            OnEnter(GlobalGameState::Loading),
+            (loading_setup, check_levels),
+        )
+        .add_systems(
+            Update,
+            (track_loading, update_progress_bar, show_failures)
+                .chain()
+                .run_if(in_state(GlobalGameState::Loading)),
+        );
+}
+
+fn loading_setup(mut commands: Commands, theme: Res<Theme>, locale: Res<Locale>) {
+    commands
+        .spawn((
+            DespawnOnExit(GlobalGameState::Loading),
+            theme.background_color(ThemeColor::MenuBackground),
+            Node {
+                width: percent(100),
+                height: percent(100),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: px(20),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                locale.text("loading"),
+                TextFont {
+                    font_size: 36.0,
+                    ..default()
+                },
+                theme.text_color(ThemeColor::Text),
+            ));
+            parent
+                .spawn((
+                    theme.background_color(ThemeColor::Button),
+                    Node {
+                        width: px(400),
+                        height: px(20),
+                        ..default()
+                    },
+                ))
+                .with_child((
+                    ProgressBar,
+                    theme.background_color(ThemeColor::Text),
+                    Node {
+                        width: percent(0),
+                        height: percent(100),
+                        ..default()
+                    },
+                ));
+            parent.spawn((
+                FailureText,
+                Text::default(),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextLayout::new_with_justify(Justify::Center),
+                theme.text_color(ThemeColor::Text),
+            ));
+        });
+}
+
+fn check_levels(mut preload: ResMut<Preload>) {
+    let names = match Level::names() {
+        Ok(names) => names,
+        Err(error) => {
+            error!("failed to list levels: {error}");
+            return;
+        }
+    };
+    for name in names {
+        let path = Level::path(&name);
+        if let Err(error) = Level::load(&path) {
+            error!("failed to load {}: {error}", path.display());
+            preload.failed.push(format!("{}: {error}", path.display()));
+        }
+    }
+}
+
+// Assets that fail to load are listed on the loading screen, which then waits for the
+// player to carry on without them, so a missing file never blocks the menu.
+fn track_loading(
+    mut preload: ResMut<Preload>,
+    asset_server: Res<AssetServer>,
+    mut game_state: ResMut<NextState<GlobalGameState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+) {
+    let Preload {
+        pending, failed, ..
+    } = &mut *preload;
+    pending.retain(
+        |handle| match asset_server.recursive_dependency_load_state(handle) {
+            RecursiveDependencyLoadState::Loaded => false,
+            RecursiveDependencyLoadState::Failed(error) => {
+                error!("failed to load {:?}: {error}", handle.path());
+                failed.push(
+                    handle
+                        .path()
+                        .map_or_else(|| format!("{handle:?}"), ToString::to_string),
+                );
+                false
+            }
+            RecursiveDependencyLoadState::NotLoaded | RecursiveDependencyLoadState::Loading => true,
+        },
+    );
+    if !preload.pending.is_empty() {
+        return;
+    }
+    if preload.failed.is_empty() || any_button_pressed(&keyboard_input, &mouse_input, &gamepads) {
+        game_state.set(GlobalGameState::Menu);
+    }
+}
+
+fn show_failures(
+    preload: Res<Preload>,
+    mut text: Single<&mut Text, With<FailureText>>,
+    locale: Res<Locale>,
+) {
+    if preload.failed.is_empty() {
+        return;
+    }
+    let mut lines: Vec<String> = preload
+        .failed
+        .iter()
+        .map(|path| locale.format("loading-failed", [("path", path.clone().into())]))
+        .collect();
+    if preload.pending.is_empty() {
+        lines.push(locale.get("loading-continue"));
+    }
+    text.0 = lines.join("\n");
+}
+
+fn update_progress_bar(preload: Res<Preload>, mut bar: Single<&mut Node, With<ProgressBar>>) {
+    bar.width = percent(preload.progress() * 100.0);
+}
@@ -0,0 +1,155 @@
+use super::asset_dir;
+use bevy::prelude::*;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use std::fs;
+use unic_langid::LanguageIdentifier;
+
+const LOCALE_DIR: &str = "locales";
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub(crate) enum Language {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+impl Language {
+    const ALL: [Language; 4] = [
+        Language::English,
+        Language::French,
+        Language::German,
+        Language::Spanish,
+    ];
+
+    fn code(self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Spanish => "es",
+        }
+    }
+
+    // Always shown in the language itself so players can find their way back.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "Français",
+            Language::German => "Deutsch",
+            Language::Spanish => "Español",
+        }
+    }
+
+    pub(crate) fn cycle(self, step: isize) -> Self {
+        crate::cycle(&Self::ALL, self, step)
+    }
+}
+
+// Marks a `Text` whose content is the translation of a message id, so it can be
+// re-texted when the language changes.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct LocalizedText(pub &'static str);
+
+#[derive(Resource)]
+pub(crate) struct Locale {
+    language: Language,
+    bundle: FluentBundle<FluentResource>,
+    // Messages missing from a translation fall back to English.
+    fallback: FluentBundle<FluentResource>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::new(Language::default())
+    }
+}
+
+impl Locale {
+    pub(crate) fn new(language: Language) -> Self {
+        Self {
+            language,
+            bundle: load_bundle(language),
+            fallback: load_bundle(Language::English),
+        }
+    }
+
+    pub(crate) fn language(&self) -> Language {
+        self.language
+    }
+
+    pub(crate) fn get(&self, id: &str) -> String {
+        self.format(id, [])
+    }
+
+    pub(crate) fn format<'a, const N: usize>(
+        &self,
+        id: &str,
+        args: [(&'a str, FluentValue<'a>); N],
+    ) -> String {
+        let args = FluentArgs::from_iter(args);
+        [&self.bundle, &self.fallback]
+            .into_iter()
+            .find_map(|bundle| {
+                let pattern = bundle.get_message(id)?.value()?;
+                let mut errors = Vec::new();
+                let text = bundle.format_pattern(pattern, Some(&args), &mut errors);
+                for error in errors {
+                    warn!("failed to format message `{id}`: {error}");
+                }
+                Some(text.into_owned())
+            })
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    pub(crate) fn text(&self, id: &'static str) -> (Text, LocalizedText) {
+        (Text::new(self.get(id)), LocalizedText(id))
+    }
+}
+
+fn load_bundle(language: Language) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = language.code().parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new_concurrent(vec![id]);
+    // Unicode isolation marks render as boxes in the default font.
+    bundle.set_use_isolating(false);
+
+    let path = asset_dir()
+        .join(LOCALE_DIR)
+        .join(language.code())
+        .join("main.ftl");
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(error) => {
+            warn!("failed to read {}: {error}", path.display());
+            return bundle;
+        }
+    };
+    let resource = FluentResource::try_new(source).unwrap_or_else(|(resource, errors)| {
+        for error in errors {
+            warn!("failed to parse {}: {error}", path.display());
+        }
+        resource
+    });
+    if let Err(errors) = bundle.add_resource(resource) {
+        for error in errors {
+            warn!("duplicate message in {}: {error}", path.display());
+        }
+    }
+    bundle
+}
+
+pub fn locale_plugin(app: &mut App) {
+    app.init_resource::<Locale>()
+        .add_systems(PostUpdate, retext);
+}
+
+fn retext(locale: Res<Locale>, mut text_query: Query<(&LocalizedText, &mut Text)>) {
+    if !locale.is_changed() {
+        return;
+    }
+    for (id, mut text) in &mut text_query {
+        text.0 = locale.get(id.0);
+    }
+}
@@ -1,20 +1,72 @@
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::asset::io::file::FileAssetReader;
 use bevy::prelude::*;
+use bevy::render::settings::{RenderCreation, WgpuSettings};
+use bevy::render::RenderPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::WindowMode;
+use bevy::winit::WinitPlugin;
+use config::Config;
+use level::Level;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+mod achievements;
+mod cli;
+mod config;
 mod game;
+mod level;
+mod loading;
+mod locale;
 mod menu;
+mod net;
 mod splash;
+mod stats;
+mod theme;
 
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
 enum GlobalGameState {
     #[default]
     Splash,
+    Loading,
     Menu,
     Game,
+    Editor,
+}
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Serialize, Deserialize)]
+enum GameMode {
+    #[default]
+    Solo,
+    Coop,
+    Versus,
+    TimeAttack,
+    Endless,
+}
+
+impl GameMode {
+    fn players(self) -> usize {
+        match self {
+            GameMode::Solo | GameMode::TimeAttack | GameMode::Endless => 1,
+            GameMode::Coop | GameMode::Versus => 2,
+        }
+    }
 }
 
 #[derive(Resource)]
 struct GameSettings {
     brick_rows: usize,
     brick_columns: usize,
+    paddle_spin: f32,
+    countdown_seconds: usize,
+    // Zero leaves time attack runs untimed, otherwise the clock counts down from it.
+    time_limit_minutes: usize,
+    mode: GameMode,
+    online: bool,
+    level: Option<Level>,
+    brick_symbols: bool,
+    seed: Option<u64>,
 }
 
 impl Default for GameSettings {
@@ -22,29 +74,121 @@ impl Default for GameSettings {
         Self {
             brick_rows: 5,
             brick_columns: 10,
+            paddle_spin: 0.3,
+            countdown_seconds: 3,
+            time_limit_minutes: 0,
+            mode: GameMode::Solo,
+            online: false,
+            level: None,
+            brick_symbols: false,
+            seed: None,
         }
     }
 }
 
-fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Bevy Breakout".to_string(),
-                resizable: false,
-                position: WindowPosition::Centered(MonitorSelection::Primary),
-                // mode: WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
-                ..default()
-            }),
-            ..default()
-        }))
-        .insert_resource(ClearColor(Color::srgb(0.95, 0.95, 0.95)))
-        .insert_resource(Time::<Fixed>::from_hz(120.0))
-        .init_state::<GlobalGameState>()
-        .init_resource::<GameSettings>()
+const FIXED_HZ: f64 = 120.0;
+// Where the `AssetServer` finds its files. Assets read straight from disk are looked up
+// here as well, so they are found whatever the working directory is.
+fn asset_dir() -> PathBuf {
+    FileAssetReader::get_base_path().join("assets")
+}
+
+// Achievements, statistics and saved games live next to the assets, for the same reason.
+fn save_dir() -> PathBuf {
+    FileAssetReader::get_base_path().join("save")
+}
+
+fn main() -> AppExit {
+    let options = match cli::Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{message}\n\n{}", cli::USAGE);
+            return AppExit::error();
+        }
+    };
+    if options.help {
+        println!("{}", cli::USAGE);
+        return AppExit::Success;
+    }
+    let config = Config::load(options.config.as_deref())
+        .map_err(|error| format!("failed to read config: {error}"))
+        .and_then(|mut config| options.apply(&mut config).map(|_| config));
+    let config = match config {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("{message}");
+            return AppExit::error();
+        }
+    };
+
+    let window = Window {
+        title: "Bevy Breakout".to_string(),
+        resizable: false,
+        position: WindowPosition::Centered(MonitorSelection::Primary),
+        mode: if config.fullscreen {
+            WindowMode::BorderlessFullscreen(MonitorSelection::Primary)
+        } else {
+            WindowMode::Windowed
+        },
+        ..default()
+    };
+    let plugins = DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(window),
+        ..default()
+    });
+
+    let mut app = App::new();
+    match options.headless {
+        // The window entity stays so layout code keeps working, but nothing opens or renders
+        // it, and every update advances exactly one game tick.
+        Some(ticks) => {
+            app.add_plugins(
+                plugins
+                    .set(RenderPlugin {
+                        render_creation: RenderCreation::Automatic(WgpuSettings {
+                            backends: None,
+                            ..default()
+                        }),
+                        ..default()
+                    })
+                    .set(ScheduleRunnerPlugin::run_loop(Duration::ZERO))
+                    .disable::<WinitPlugin>(),
+            )
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / FIXED_HZ,
+            )))
+            .insert_resource(game::HeadlessRun::new(ticks));
+        }
+        None => {
+            app.add_plugins(plugins);
+        }
+    }
+
+    app.insert_resource(Time::<Fixed>::from_hz(FIXED_HZ))
+        .insert_state(options.initial_state())
+        .insert_resource(config.settings)
         .add_systems(Startup, setup)
-        .add_plugins((splash::splash_plugin, menu::menu_plugin, game::game_plugin))
-        .run();
+        .add_plugins((
+            theme::theme_plugin,
+            locale::locale_plugin,
+            achievements::achievements_plugin,
+            stats::stats_plugin,
+            splash::splash_plugin,
+            loading::loading_plugin,
+            menu::menu_plugin,
+            game::game_plugin,
+            net::net_plugin,
+        ))
+        .run()
+}
+
+fn cycle<T: Copy + PartialEq>(options: &[T], current: T, step: isize) -> T {
+    let index = options
+        .iter()
+        .position(|option| *option == current)
+        .unwrap_or(0);
+    let len = options.len() as isize;
+    options[(index as isize + step).rem_euclid(len) as usize]
 }
 
 fn setup(mut commands: Commands) {
@@ -1,5 +1,15 @@
-use super::{GameSettings, GlobalGameState};
+use super::achievements::{Achievement, Achievements};
+use super::game::{has_saved_game, ContinueGame};
+use super::level::{MAX_COLUMNS, MAX_ROWS};
+use super::locale::Locale;
+use super::net::NetConfig;
+use super::stats::{format_duration, Stats};
+use super::theme::{Theme, ThemeColor};
+use super::{GameMode, GameSettings, GlobalGameState};
 use bevy::prelude::*;
+use navigation::SettingRow;
+
+mod navigation;
 
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
 enum MenuState {
@@ -7,6 +17,8 @@ enum MenuState {
     Menu,
     Main,
     Settings,
+    Achievements,
+    Statistics,
 }
 
 #[derive(Component)]
@@ -18,37 +30,68 @@ enum SettingButton {
     RowsDec,
     ColsInc,
     ColsDec,
+    CountdownInc,
+    CountdownDec,
+    TimeLimitInc,
+    TimeLimitDec,
+    ThemePrev,
+    ThemeNext,
+    LanguagePrev,
+    LanguageNext,
+    PalettePrev,
+    PaletteNext,
+    SymbolsToggle,
     Back,
+    Continue,
     Play,
+    Coop,
+    Versus,
+    TimeAttack,
+    Endless,
+    Online,
+    Editor,
     Settings,
+    Achievements,
+    Statistics,
 }
 
 #[derive(Component)]
 enum SettingLabel {
     Rows,
     Cols,
+    Countdown,
+    TimeLimit,
+    Theme,
+    Language,
+    Palette,
+    Symbols,
 }
 
 pub fn menu_plugin(app: &mut App) {
     app.add_systems(OnEnter(GlobalGameState::Menu), menu_setup)
         .add_systems(OnEnter(MenuState::Main), main_menu_setup)
         .add_systems(OnEnter(MenuState::Settings), settings_menu_setup)
+        .add_systems(OnEnter(MenuState::Achievements), achievements_menu_setup)
+        .add_systems(OnEnter(MenuState::Statistics), statistics_menu_setup)
         .add_systems(
             Update,
-            (
-                button_system.run_if(in_state(GlobalGameState::Menu)),
-                update_settings_labels.run_if(in_state(GlobalGameState::Menu)),
-            ),
+            (button_system, setting_button_system, update_settings_labels),
         )
+        .add_plugins(navigation::navigation_plugin)
         .init_state::<MenuState>();
 }
 
-fn menu_setup(mut commands: Commands, mut menu_state: ResMut<NextState<MenuState>>) {
+fn menu_setup(
+    mut commands: Commands,
+    mut menu_state: ResMut<NextState<MenuState>>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+) {
     commands
         .spawn((
             Menu,
             DespawnOnExit(GlobalGameState::Menu),
-            BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+            theme.background_color(ThemeColor::MenuBackground),
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
@@ -60,19 +103,25 @@ fn menu_setup(mut commands: Commands, mut menu_state: ResMut<NextState<MenuState
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new("BEVY BREAKOUT"),
+                locale.text("game-title"),
                 TextFont {
                     font_size: 80.0,
                     ..default()
                 },
-                TextColor(Color::WHITE),
+                theme.text_color(ThemeColor::Text),
             ));
         });
 
     menu_state.set(MenuState::Main);
 }
 
-fn main_menu_setup(mut commands: Commands, menu: Single<Entity, With<Menu>>) {
+fn main_menu_setup(
+    mut commands: Commands,
+    menu: Single<Entity, With<Menu>>,
+    net_config: Option<Res<NetConfig>>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+) {
     commands.entity(menu.entity()).with_children(|parent| {
         parent
             .spawn((
@@ -87,15 +136,228 @@ fn main_menu_setup(mut commands: Commands, menu: Single<Entity, With<Menu>>) {
             ))
             .with_children(|col| {
                 col.spawn((
-                    Text::new("MAIN MENU"),
+                    locale.text("main-menu"),
                     TextFont {
                         font_size: 60.0,
                         ..default()
                     },
-                    TextColor(Color::WHITE),
+                    theme.text_color(ThemeColor::Text),
                 ));
-                spawn_button(col, "Play", 150.0, 30.0, SettingButton::Play);
-                spawn_button(col, "Settings", 150.0, 30.0, SettingButton::Settings);
+                if has_saved_game() {
+                    spawn_button(
+                        col,
+                        &theme,
+                        &locale,
+                        "menu-continue",
+                        150.0,
+                        30.0,
+                        SettingButton::Continue,
+                    );
+                }
+                let mut modes = vec![
+                    ("menu-play", SettingButton::Play),
+                    ("menu-coop", SettingButton::Coop),
+                    ("menu-versus", SettingButton::Versus),
+                    ("menu-time-attack", SettingButton::TimeAttack),
+                    ("menu-endless", SettingButton::Endless),
+                ];
+                if net_config.is_some() {
+                    modes.push(("menu-online", SettingButton::Online));
+                }
+                let others = vec![
+                    ("menu-editor", SettingButton::Editor),
+                    ("menu-achievements", SettingButton::Achievements),
+                    ("menu-statistics", SettingButton::Statistics),
+                    ("menu-settings", SettingButton::Settings),
+                ];
+                spawn_button_grid(col, &theme, &locale, 3, modes);
+                spawn_button_grid(col, &theme, &locale, 2, others);
+            });
+    });
+}
+
+// A single column of every button would not fit a 720 pixel high window, so the main
+// menu lays its buttons out in rows of `columns`.
+fn spawn_button_grid(
+    parent: &mut ChildSpawnerCommands,
+    theme: &Theme,
+    locale: &Locale,
+    columns: u16,
+    buttons: Vec<(&'static str, SettingButton)>,
+) {
+    parent
+        .spawn(Node {
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::auto(columns),
+            column_gap: px(20.0),
+            row_gap: px(20.0),
+            ..default()
+        })
+        .with_children(|grid| {
+            for (text, button) in buttons {
+                spawn_button(grid, theme, locale, text, 150.0, 30.0, button);
+            }
+        });
+}
+
+fn achievements_menu_setup(
+    mut commands: Commands,
+    menu: Single<Entity, With<Menu>>,
+    achievements: Res<Achievements>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+) {
+    commands.entity(menu.entity()).with_children(|parent| {
+        parent
+            .spawn((
+                DespawnOnExit(MenuState::Achievements),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: px(16.0),
+                    margin: UiRect::top(px(23.0)),
+                    ..default()
+                },
+            ))
+            .with_children(|col| {
+                col.spawn((
+                    locale.text("achievements-title"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    theme.text_color(ThemeColor::Text),
+                ));
+                for achievement in Achievement::ALL {
+                    // Locked achievements are dimmed but still say how to earn them.
+                    let color = if achievements.is_unlocked(achievement) {
+                        ThemeColor::Text
+                    } else {
+                        ThemeColor::MutedText
+                    };
+                    col.spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    })
+                    .with_children(|entry| {
+                        entry.spawn((
+                            locale.text(achievement.name_id()),
+                            TextFont {
+                                font_size: 26.0,
+                                ..default()
+                            },
+                            theme.text_color(color),
+                        ));
+                        entry.spawn((
+                            locale.text(achievement.description_id()),
+                            TextFont {
+                                font_size: 18.0,
+                                ..default()
+                            },
+                            theme.text_color(ThemeColor::MutedText),
+                        ));
+                    });
+                }
+                spawn_button(
+                    col,
+                    &theme,
+                    &locale,
+                    "menu-back",
+                    100.0,
+                    30.0,
+                    SettingButton::Back,
+                );
+            });
+    });
+}
+
+fn statistics_menu_setup(
+    mut commands: Commands,
+    menu: Single<Entity, With<Menu>>,
+    stats: Res<Stats>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+) {
+    let rows = [
+        ("stat-games-played", stats.games_played.to_string()),
+        ("stat-play-time", format_duration(stats.play_time)),
+        ("stat-bricks-broken", stats.bricks_broken.to_string()),
+        ("stat-balls-lost", stats.balls_lost.to_string()),
+        ("stat-paddle-hits", stats.paddle_hits.to_string()),
+        ("stat-longest-rally", stats.longest_rally.to_string()),
+        (
+            "stat-fastest-clear",
+            stats
+                .fastest_clear
+                .map_or("-".to_string(), |seconds| format_duration(seconds.into())),
+        ),
+        ("stat-endless-rows", stats.most_endless_rows.to_string()),
+    ];
+
+    commands.entity(menu.entity()).with_children(|parent| {
+        parent
+            .spawn((
+                DespawnOnExit(MenuState::Statistics),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: px(16.0),
+                    margin: UiRect::top(px(23.0)),
+                    ..default()
+                },
+            ))
+            .with_children(|col| {
+                col.spawn((
+                    locale.text("statistics-title"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    theme.text_color(ThemeColor::Text),
+                ));
+                for (label, value) in rows {
+                    col.spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: px(16.0),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            locale.text(label),
+                            TextFont {
+                                font_size: 26.0,
+                                ..default()
+                            },
+                            theme.text_color(ThemeColor::MutedText),
+                            Node {
+                                width: px(240.0),
+                                ..default()
+                            },
+                        ));
+                        row.spawn((
+                            Text::new(value),
+                            TextFont {
+                                font_size: 26.0,
+                                ..default()
+                            },
+                            theme.text_color(ThemeColor::Text),
+                            Node {
+                                width: px(120.0),
+                                ..default()
+                            },
+                        ));
+                    });
+                }
+                spawn_button(
+                    col,
+                    &theme,
+                    &locale,
+                    "menu-back",
+                    100.0,
+                    30.0,
+                    SettingButton::Back,
+                );
             });
     });
 }
@@ -104,6 +366,8 @@ fn settings_menu_setup(
     mut commands: Commands,
     menu: Single<Entity, With<Menu>>,
     settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
 ) {
     commands.entity(menu.entity()).with_children(|parent| {
         parent
@@ -119,42 +383,143 @@ fn settings_menu_setup(
             ))
             .with_children(|col| {
                 col.spawn((
-                    Text::new("SETTINGS"),
+                    locale.text("settings-title"),
                     TextFont {
                         font_size: 36.0,
                         ..default()
                     },
-                    TextColor(Color::WHITE),
+                    theme.text_color(ThemeColor::Text),
                 ));
-                spawn_setting_row(
-                    col,
-                    "Rows",
-                    settings.brick_rows,
-                    SettingButton::RowsInc,
-                    SettingButton::RowsDec,
-                    SettingLabel::Rows,
-                );
-                spawn_setting_row(
+                spawn_setting_rows(col, &settings, &theme, &locale);
+                spawn_button(
                     col,
-                    "Columns",
-                    settings.brick_columns,
-                    SettingButton::ColsInc,
-                    SettingButton::ColsDec,
-                    SettingLabel::Cols,
+                    &theme,
+                    &locale,
+                    "menu-back",
+                    100.0,
+                    30.0,
+                    SettingButton::Back,
                 );
-                spawn_button(col, "Back", 100.0, 30.0, SettingButton::Back);
             });
     });
 }
 
+pub(crate) fn spawn_setting_rows(
+    parent: &mut ChildSpawnerCommands,
+    settings: &GameSettings,
+    theme: &Theme,
+    locale: &Locale,
+) {
+    spawn_setting_row(
+        parent,
+        theme,
+        locale,
+        "setting-rows",
+        settings.brick_rows.to_string(),
+        SettingButton::RowsInc,
+        SettingButton::RowsDec,
+        SettingLabel::Rows,
+    );
+    spawn_setting_row(
+        parent,
+        theme,
+        locale,
+        "setting-columns",
+        settings.brick_columns.to_string(),
+        SettingButton::ColsInc,
+        SettingButton::ColsDec,
+        SettingLabel::Cols,
+    );
+    spawn_setting_row(
+        parent,
+        theme,
+        locale,
+        "setting-countdown",
+        settings.countdown_seconds.to_string(),
+        SettingButton::CountdownInc,
+        SettingButton::CountdownDec,
+        SettingLabel::Countdown,
+    );
+    spawn_setting_row(
+        parent,
+        theme,
+        locale,
+        "setting-time-limit",
+        time_limit_text(settings, locale),
+        SettingButton::TimeLimitInc,
+        SettingButton::TimeLimitDec,
+        SettingLabel::TimeLimit,
+    );
+    spawn_setting_row(
+        parent,
+        theme,
+        locale,
+        "setting-theme",
+        locale.get(theme.kind.message_id()),
+        SettingButton::ThemeNext,
+        SettingButton::ThemePrev,
+        SettingLabel::Theme,
+    );
+    spawn_setting_row(
+        parent,
+        theme,
+        locale,
+        "setting-language",
+        locale.language().name().to_string(),
+        SettingButton::LanguageNext,
+        SettingButton::LanguagePrev,
+        SettingLabel::Language,
+    );
+    spawn_setting_row(
+        parent,
+        theme,
+        locale,
+        "setting-palette",
+        locale.get(theme.palette.message_id()),
+        SettingButton::PaletteNext,
+        SettingButton::PalettePrev,
+        SettingLabel::Palette,
+    );
+    spawn_setting_row(
+        parent,
+        theme,
+        locale,
+        "setting-brick-symbols",
+        locale.get(on_off(settings.brick_symbols)),
+        SettingButton::SymbolsToggle,
+        SettingButton::SymbolsToggle,
+        SettingLabel::Symbols,
+    );
+}
+
+fn time_limit_text(settings: &GameSettings, locale: &Locale) -> String {
+    match settings.time_limit_minutes {
+        0 => locale.get("setting-off"),
+        minutes => locale.format("setting-minutes", [("minutes", minutes.into())]),
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "setting-on"
+    } else {
+        "setting-off"
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn spawn_setting_row(
     parent: &mut ChildSpawnerCommands,
-    label: &str,
-    value: usize,
+    theme: &Theme,
+    locale: &Locale,
+    label: &'static str,
+    value: String,
     inc_button: SettingButton,
     dec_button: SettingButton,
     label_value: SettingLabel,
 ) {
+    let mut decrease = Entity::PLACEHOLDER;
+    let mut increase = Entity::PLACEHOLDER;
     parent
         .spawn(Node {
             flex_direction: FlexDirection::Row,
@@ -164,48 +529,67 @@ fn spawn_setting_row(
         })
         .with_children(|row| {
             row.spawn((
-                Text::new(format!("{label}")),
+                locale.text(label),
                 TextFont {
                     font_size: 26.0,
                     ..default()
                 },
-                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                theme.text_color(ThemeColor::MutedText),
                 Node {
-                    width: Val::Px(100.0),
+                    width: Val::Px(200.0),
                     ..default()
                 },
             ));
-            spawn_button(row, "-", 36.0, 36.0, dec_button);
+            decrease = spawn_button(
+                row,
+                theme,
+                locale,
+                "setting-decrease",
+                36.0,
+                36.0,
+                dec_button,
+            );
             row.spawn((
                 label_value,
-                Text::new(format!("{value}")),
+                Text::new(value),
                 TextFont {
                     font_size: 26.0,
                     ..default()
                 },
-                TextColor(Color::WHITE),
+                theme.text_color(ThemeColor::Text),
                 Node {
-                    width: Val::Px(40.0),
+                    width: Val::Px(120.0),
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
             ));
-            spawn_button(row, "+", 36.0, 36.0, inc_button);
-        });
+            increase = spawn_button(
+                row,
+                theme,
+                locale,
+                "setting-increase",
+                36.0,
+                36.0,
+                inc_button,
+            );
+        })
+        .insert(SettingRow { decrease, increase });
 }
 
-fn spawn_button(
+pub(crate) fn spawn_button(
     parent: &mut ChildSpawnerCommands,
-    text: &str,
+    theme: &Theme,
+    locale: &Locale,
+    text: &'static str,
     width: f32,
     height: f32,
-    button: SettingButton,
-) {
+    button: impl Component,
+) -> Entity {
     parent
         .spawn((
             button,
             Button,
-            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+            theme.background_color(ThemeColor::Button),
             Node {
                 width: Val::Px(width),
                 height: Val::Px(height),
@@ -216,52 +600,125 @@ fn spawn_button(
         ))
         .with_children(|btn| {
             btn.spawn((
-                Text::new(text),
+                locale.text(text),
                 TextFont {
                     font_size: 24.0,
                     ..default()
                 },
-                TextColor(Color::WHITE),
+                theme.text_color(ThemeColor::Text),
             ));
-        });
+        })
+        .id()
 }
 
 fn button_system(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &SettingButton),
-        Changed<Interaction>,
-    >,
-    mut settings: ResMut<GameSettings>,
-    mut game_state: ResMut<NextState<GlobalGameState>>,
-    mut menu_state: ResMut<NextState<MenuState>>,
+    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), Changed<Interaction>>,
+    theme: Res<Theme>,
 ) {
-    for (interaction, mut color, button) in &mut interaction_query {
+    for (interaction, mut color) in &mut interaction_query {
         match *interaction {
             Interaction::Pressed => {
-                *color = BackgroundColor(Color::srgb(0.4, 0.4, 0.4));
-                match button {
-                    SettingButton::Play => game_state.set(GlobalGameState::Game),
-                    SettingButton::Back => menu_state.set(MenuState::Main),
-                    SettingButton::Settings => menu_state.set(MenuState::Settings),
-                    SettingButton::RowsInc => {
-                        settings.brick_rows = (settings.brick_rows + 1).min(10)
-                    }
-                    SettingButton::RowsDec => {
-                        settings.brick_rows = (settings.brick_rows - 1).max(1)
-                    }
-                    SettingButton::ColsInc => {
-                        settings.brick_columns = (settings.brick_columns + 1).min(20)
-                    }
-                    SettingButton::ColsDec => {
-                        settings.brick_columns = (settings.brick_columns - 1).max(1)
-                    }
-                }
+                *color = BackgroundColor(theme.button_pressed);
             }
             Interaction::Hovered => {
-                *color = BackgroundColor(Color::srgb(0.5, 0.5, 0.5));
+                *color = BackgroundColor(theme.button_hovered);
             }
             Interaction::None => {
-                *color = BackgroundColor(Color::srgb(0.3, 0.3, 0.3));
+                *color = BackgroundColor(theme.button);
+            }
+        }
+    }
+}
+
+fn setting_button_system(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &SettingButton), Changed<Interaction>>,
+    mut settings: ResMut<GameSettings>,
+    mut theme: ResMut<Theme>,
+    mut locale: ResMut<Locale>,
+    mut game_state: ResMut<NextState<GlobalGameState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match button {
+                SettingButton::Continue => commands.trigger(ContinueGame),
+                SettingButton::Play => {
+                    settings.mode = GameMode::Solo;
+                    settings.online = false;
+                    settings.level = None;
+                    game_state.set(GlobalGameState::Game);
+                }
+                SettingButton::Coop => {
+                    settings.mode = GameMode::Coop;
+                    settings.online = false;
+                    settings.level = None;
+                    game_state.set(GlobalGameState::Game);
+                }
+                SettingButton::Versus => {
+                    settings.mode = GameMode::Versus;
+                    settings.online = false;
+                    settings.level = None;
+                    game_state.set(GlobalGameState::Game);
+                }
+                SettingButton::TimeAttack => {
+                    settings.mode = GameMode::TimeAttack;
+                    settings.online = false;
+                    settings.level = None;
+                    game_state.set(GlobalGameState::Game);
+                }
+                SettingButton::Endless => {
+                    settings.mode = GameMode::Endless;
+                    settings.online = false;
+                    settings.level = None;
+                    game_state.set(GlobalGameState::Game);
+                }
+                SettingButton::Online => {
+                    settings.mode = GameMode::Versus;
+                    settings.online = true;
+                    settings.level = None;
+                    game_state.set(GlobalGameState::Game);
+                }
+                SettingButton::Editor => game_state.set(GlobalGameState::Editor),
+                SettingButton::Back => menu_state.set(MenuState::Main),
+                SettingButton::Settings => menu_state.set(MenuState::Settings),
+                SettingButton::Achievements => menu_state.set(MenuState::Achievements),
+                SettingButton::Statistics => menu_state.set(MenuState::Statistics),
+                SettingButton::RowsInc => {
+                    settings.brick_rows = (settings.brick_rows + 1).min(MAX_ROWS)
+                }
+                SettingButton::RowsDec => settings.brick_rows = (settings.brick_rows - 1).max(1),
+                SettingButton::ColsInc => {
+                    settings.brick_columns = (settings.brick_columns + 1).min(MAX_COLUMNS)
+                }
+                SettingButton::ColsDec => {
+                    settings.brick_columns = (settings.brick_columns - 1).max(1)
+                }
+                SettingButton::CountdownInc => {
+                    settings.countdown_seconds = (settings.countdown_seconds + 1).min(5)
+                }
+                SettingButton::CountdownDec => {
+                    settings.countdown_seconds = settings.countdown_seconds.saturating_sub(1)
+                }
+                SettingButton::TimeLimitInc => {
+                    settings.time_limit_minutes = (settings.time_limit_minutes + 1).min(10)
+                }
+                SettingButton::TimeLimitDec => {
+                    settings.time_limit_minutes = settings.time_limit_minutes.saturating_sub(1)
+                }
+                SettingButton::ThemeNext => *theme = Theme::new(theme.kind.cycle(1), theme.palette),
+                SettingButton::ThemePrev => {
+                    *theme = Theme::new(theme.kind.cycle(-1), theme.palette)
+                }
+                SettingButton::PaletteNext => {
+                    *theme = Theme::new(theme.kind, theme.palette.cycle(1))
+                }
+                SettingButton::PalettePrev => {
+                    *theme = Theme::new(theme.kind, theme.palette.cycle(-1))
+                }
+                SettingButton::SymbolsToggle => settings.brick_symbols = !settings.brick_symbols,
+                SettingButton::LanguageNext => *locale = Locale::new(locale.language().cycle(1)),
+                SettingButton::LanguagePrev => *locale = Locale::new(locale.language().cycle(-1)),
             }
         }
     }
@@ -269,15 +726,23 @@ fn button_system(
 
 fn update_settings_labels(
     settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
     mut label_query: Query<(&SettingLabel, &mut Text)>,
 ) {
-    if !settings.is_changed() {
+    if !settings.is_changed() && !theme.is_changed() && !locale.is_changed() {
         return;
     }
     for (label, mut text) in &mut label_query {
         match label {
             SettingLabel::Rows => **text = settings.brick_rows.to_string(),
             SettingLabel::Cols => **text = settings.brick_columns.to_string(),
+            SettingLabel::Countdown => **text = settings.countdown_seconds.to_string(),
+            SettingLabel::TimeLimit => **text = time_limit_text(&settings, &locale),
+            SettingLabel::Theme => **text = locale.get(theme.kind.message_id()),
+            SettingLabel::Language => **text = locale.language().name().to_string(),
+            SettingLabel::Palette => **text = locale.get(theme.palette.message_id()),
+            SettingLabel::Symbols => **text = locale.get(on_off(settings.brick_symbols)),
         }
     }
 }
@@ -0,0 +1,268 @@
+use crate::theme::Theme;
+use bevy::prelude::*;
+use bevy::ui::UiSystems;
+
+type Focusable = Or<(With<Button>, With<SettingRow>)>;
+
+// A setting row is focused as a whole; left and right press its buttons.
+#[derive(Component)]
+pub(super) struct SettingRow {
+    pub(super) decrease: Entity,
+    pub(super) increase: Entity,
+}
+
+#[derive(Resource, Default)]
+struct MenuFocus {
+    focused: Option<Entity>,
+    // Buttons pressed from the keyboard or a gamepad are released the next frame, the
+    // same way a click would be.
+    pressed: Option<Entity>,
+}
+
+// Tab walks every entry in reading order, while the arrows move around the screen.
+enum NavAction {
+    Up,
+    Down,
+    Previous,
+    Next,
+    Decrease,
+    Increase,
+    Activate,
+}
+
+pub(super) fn navigation_plugin(app: &mut App) {
+    app.init_resource::<MenuFocus>()
+        .add_systems(
+            PreUpdate,
+            (release_pressed, navigate_menus)
+                .chain()
+                .after(UiSystems::Focus),
+        )
+        .add_systems(Update, highlight_focus);
+}
+
+fn nav_action(
+    keyboard_input: &ButtonInput<KeyCode>,
+    gamepads: &Query<&Gamepad>,
+) -> Option<NavAction> {
+    let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let key = |key| keyboard_input.just_pressed(key);
+    let button = |button| gamepads.iter().any(|gamepad| gamepad.just_pressed(button));
+
+    if key(KeyCode::ArrowUp) || button(GamepadButton::DPadUp) {
+        Some(NavAction::Up)
+    } else if key(KeyCode::ArrowDown) || button(GamepadButton::DPadDown) {
+        Some(NavAction::Down)
+    } else if shift && key(KeyCode::Tab) {
+        Some(NavAction::Previous)
+    } else if key(KeyCode::Tab) {
+        Some(NavAction::Next)
+    } else if key(KeyCode::ArrowLeft) || button(GamepadButton::DPadLeft) {
+        Some(NavAction::Decrease)
+    } else if key(KeyCode::ArrowRight) || button(GamepadButton::DPadRight) {
+        Some(NavAction::Increase)
+    } else if key(KeyCode::Enter) || key(KeyCode::NumpadEnter) || button(GamepadButton::South) {
+        Some(NavAction::Activate)
+    } else {
+        None
+    }
+}
+
+// Entries whose centres are this close vertically sit on the same line.
+const SAME_LINE: f32 = 1.0;
+
+// The entry on the nearest line above or below, wrapping around at the ends, picking the
+// one closest horizontally so moving through a grid keeps to its column.
+fn vertical_neighbour(focusables: &[(Entity, Vec2)], index: usize, down: bool) -> usize {
+    let from = focusables[index].1;
+    // How far down the screen each entry is from here, flipped when moving up.
+    let ahead = |position: Vec2| {
+        if down {
+            position.y - from.y
+        } else {
+            from.y - position.y
+        }
+    };
+    let nearest = focusables
+        .iter()
+        .map(|(_, position)| ahead(*position))
+        .filter(|distance| *distance > SAME_LINE)
+        .reduce(f32::min);
+    // Past the last line, go round to the first one.
+    let line = nearest.unwrap_or_else(|| {
+        focusables
+            .iter()
+            .map(|(_, position)| ahead(*position))
+            .fold(0.0, f32::min)
+    });
+    focusables
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, position))| (ahead(*position) - line).abs() <= SAME_LINE)
+        .min_by(|(_, (_, a)), (_, (_, b))| (a.x - from.x).abs().total_cmp(&(b.x - from.x).abs()))
+        .map_or(index, |(neighbour, _)| neighbour)
+}
+
+// The entry beside this one on its line, if there is one in that direction.
+fn horizontal_neighbour(focusables: &[(Entity, Vec2)], index: usize, right: bool) -> Option<usize> {
+    // Entries are sorted by line, then left to right.
+    let neighbour = if right {
+        index + 1
+    } else {
+        index.checked_sub(1)?
+    };
+    focusables
+        .get(neighbour)
+        .filter(|(_, position)| (position.y - focusables[index].1.y).abs() <= SAME_LINE)
+        .map(|_| neighbour)
+}
+
+fn release_pressed(mut focus: ResMut<MenuFocus>, mut interaction_query: Query<&mut Interaction>) {
+    let Some(entity) = focus.pressed.take() else {
+        return;
+    };
+    if let Ok(mut interaction) = interaction_query.get_mut(entity) {
+        interaction.set_if_neq(Interaction::None);
+    }
+}
+
+fn navigate_menus(
+    mut focus: ResMut<MenuFocus>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    focusable_query: Query<
+        (Entity, &ComputedNode, &UiGlobalTransform, Option<&ChildOf>),
+        Focusable,
+    >,
+    row_query: Query<&SettingRow>,
+    mut interaction_query: Query<&mut Interaction>,
+) {
+    // Buttons inside a setting row are reached through the row. Nodes that haven't been
+    // laid out yet are skipped so a freshly spawned menu starts on its top entry.
+    let mut focusables: Vec<(Entity, Vec2)> = focusable_query
+        .iter()
+        .filter(|(.., child_of)| {
+            !child_of.is_some_and(|child_of| row_query.contains(child_of.parent()))
+        })
+        .filter(|(_, node, ..)| node.size() != Vec2::ZERO)
+        .map(|(entity, _, transform, _)| (entity, transform.translation))
+        .collect();
+    if focusables.is_empty() {
+        if focus.focused.is_some() {
+            focus.focused = None;
+        }
+        return;
+    }
+    focusables.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    let Some(index) = focus
+        .focused
+        .and_then(|focused| focusables.iter().position(|(entity, _)| *entity == focused))
+    else {
+        focus.focused = Some(focusables[0].0);
+        return;
+    };
+    let Some(action) = nav_action(&keyboard_input, &gamepads) else {
+        return;
+    };
+
+    let focused = focusables[index].0;
+    let target = match (action, row_query.get(focused)) {
+        (NavAction::Up, _) => {
+            focus.focused = Some(focusables[vertical_neighbour(&focusables, index, false)].0);
+            return;
+        }
+        (NavAction::Down, _) => {
+            focus.focused = Some(focusables[vertical_neighbour(&focusables, index, true)].0);
+            return;
+        }
+        (NavAction::Previous, _) => {
+            let previous = (index + focusables.len() - 1) % focusables.len();
+            focus.focused = Some(focusables[previous].0);
+            return;
+        }
+        (NavAction::Next, _) => {
+            focus.focused = Some(focusables[(index + 1) % focusables.len()].0);
+            return;
+        }
+        (NavAction::Decrease, Ok(row)) => row.decrease,
+        (NavAction::Increase | NavAction::Activate, Ok(row)) => row.increase,
+        (NavAction::Activate, Err(_)) => focused,
+        (NavAction::Decrease, Err(_)) => {
+            if let Some(neighbour) = horizontal_neighbour(&focusables, index, false) {
+                focus.focused = Some(focusables[neighbour].0);
+            }
+            return;
+        }
+        (NavAction::Increase, Err(_)) => {
+            if let Some(neighbour) = horizontal_neighbour(&focusables, index, true) {
+                focus.focused = Some(focusables[neighbour].0);
+            }
+            return;
+        }
+    };
+    if let Ok(mut interaction) = interaction_query.get_mut(target) {
+        *interaction = Interaction::Pressed;
+        focus.pressed = Some(target);
+    }
+}
+
+fn highlight_focus(
+    mut commands: Commands,
+    focus: Res<MenuFocus>,
+    theme: Res<Theme>,
+    outline_query: Query<Entity, With<Outline>>,
+) {
+    if !focus.is_changed() && !theme.is_changed() {
+        return;
+    }
+    for entity in &outline_query {
+        if Some(entity) != focus.focused {
+            commands.entity(entity).remove::<Outline>();
+        }
+    }
+    // The focused menu may be torn down by a state change before this runs.
+    if let Some(entity) = focus.focused {
+        commands
+            .entity(entity)
+            .try_insert(Outline::new(px(2.0), px(4.0), theme.text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A three-column grid of seven buttons above a two-column grid of two, in reading order.
+    fn main_menu() -> Vec<(Entity, Vec2)> {
+        let three = (0..7).map(|i| Vec2::new(100.0 * (i % 3) as f32, 40.0 * (i / 3) as f32));
+        let two = (0..2).map(|i| Vec2::new(50.0 + 100.0 * i as f32, 120.0));
+        three
+            .chain(two)
+            .enumerate()
+            .map(|(i, position)| (Entity::from_raw_u32(i as u32).unwrap(), position))
+            .collect()
+    }
+
+    #[test]
+    fn arrows_keep_to_the_column_and_wrap() {
+        let menu = main_menu();
+        assert_eq!(vertical_neighbour(&menu, 1, true), 4);
+        assert_eq!(vertical_neighbour(&menu, 4, false), 1);
+        // The last line of the grid is short, so the nearest button is taken.
+        assert_eq!(vertical_neighbour(&menu, 5, true), 6);
+        assert_eq!(vertical_neighbour(&menu, 6, true), 7);
+        // Halfway between two columns, the left one wins.
+        assert_eq!(vertical_neighbour(&menu, 8, true), 1);
+        assert_eq!(vertical_neighbour(&menu, 0, false), 7);
+    }
+
+    #[test]
+    fn left_and_right_stay_on_the_line() {
+        let menu = main_menu();
+        assert_eq!(horizontal_neighbour(&menu, 3, true), Some(4));
+        assert_eq!(horizontal_neighbour(&menu, 3, false), None);
+        assert_eq!(horizontal_neighbour(&menu, 5, true), None);
+        assert_eq!(horizontal_neighbour(&menu, 8, false), Some(7));
+    }
+}
@@ -0,0 +1,235 @@
+use super::{GameSettings, GlobalGameState};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::env;
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+const INPUT_REDUNDANCY: usize = 64;
+const PACKET_HEADER: usize = 5;
+
+#[derive(Resource, Clone, Debug)]
+pub(crate) struct NetConfig {
+    pub bind: SocketAddr,
+    pub peer: SocketAddr,
+    pub local_player: usize,
+    pub seed: u64,
+    pub packet_loss: f32,
+    pub delay: Duration,
+}
+
+impl NetConfig {
+    // BREAKOUT_BIND=127.0.0.1:7000 BREAKOUT_PEER=127.0.0.1:7001 BREAKOUT_PLAYER=0
+    // Optional: BREAKOUT_SEED, BREAKOUT_PACKET_LOSS (0.0-1.0), BREAKOUT_DELAY_MS
+    pub(crate) fn from_env() -> Option<Self> {
+        let bind = env_var("BREAKOUT_BIND")?;
+        let peer = env_var("BREAKOUT_PEER")?;
+        Some(Self {
+            bind,
+            peer,
+            local_player: env_var("BREAKOUT_PLAYER").unwrap_or(0usize).min(1),
+            seed: env_var("BREAKOUT_SEED").unwrap_or(0),
+            packet_loss: env_var("BREAKOUT_PACKET_LOSS")
+                .unwrap_or(0.0f32)
+                .clamp(0.0, 1.0),
+            delay: Duration::from_millis(env_var("BREAKOUT_DELAY_MS").unwrap_or(0)),
+        })
+    }
+}
+
+fn env_var<T: FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok()?.parse().ok()
+}
+
+// Packet layout: last frame (u32 LE), input count (u8), then one i8 input per frame
+// ending at the last frame. Only the most recent `INPUT_REDUNDANCY` inputs are sent.
+fn encode_packet(last_frame: u32, inputs: &[i8]) -> Vec<u8> {
+    let inputs = &inputs[inputs.len().saturating_sub(INPUT_REDUNDANCY)..];
+    let mut packet = Vec::with_capacity(PACKET_HEADER + inputs.len());
+    packet.extend_from_slice(&last_frame.to_le_bytes());
+    packet.push(inputs.len() as u8);
+    packet.extend(inputs.iter().map(|input| *input as u8));
+    packet
+}
+
+// Returns `(frame, input)` pairs, newest first.
+fn decode_packet(packet: &[u8]) -> Vec<(u32, i8)> {
+    if packet.len() < PACKET_HEADER {
+        return Vec::new();
+    }
+    let last_frame = u32::from_le_bytes([packet[0], packet[1], packet[2], packet[3]]);
+    let count = (packet[4] as usize).min(packet.len() - PACKET_HEADER);
+    packet[PACKET_HEADER..PACKET_HEADER + count]
+        .iter()
+        .rev()
+        .enumerate()
+        .filter_map(|(offset, input)| {
+            let frame = last_frame.checked_sub(offset as u32)?;
+            Some((frame, *input as i8))
+        })
+        .collect()
+}
+
+#[derive(Resource)]
+pub(crate) struct NetSession {
+    socket: UdpSocket,
+    peer: SocketAddr,
+    local_player: usize,
+    packet_loss: f32,
+    delay: Duration,
+    outgoing: VecDeque<(Instant, Vec<u8>)>,
+    peer_ready: bool,
+}
+
+impl NetSession {
+    pub(crate) fn bind(config: &NetConfig) -> io::Result<Self> {
+        let socket = UdpSocket::bind(config.bind)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            peer: config.peer,
+            local_player: config.local_player,
+            packet_loss: config.packet_loss,
+            delay: config.delay,
+            outgoing: VecDeque::new(),
+            peer_ready: false,
+        })
+    }
+
+    pub(crate) fn local_player(&self) -> usize {
+        self.local_player
+    }
+
+    pub(crate) fn peer_ready(&self) -> bool {
+        self.peer_ready
+    }
+
+    // Recent inputs are resent every tick to ride out packet loss.
+    pub(crate) fn send_inputs(&mut self, last_frame: u32, inputs: &[i8]) {
+        self.send(encode_packet(last_frame, inputs));
+    }
+
+    // Tells the peer we are waiting to start, as a packet without inputs. Any packet from
+    // the peer, inputs included, means it has pressed start too.
+    pub(crate) fn send_ready(&mut self) {
+        self.send(encode_packet(0, &[]));
+    }
+
+    fn send(&mut self, packet: Vec<u8>) {
+        if rand::random::<f32>() >= self.packet_loss {
+            self.outgoing
+                .push_back((Instant::now() + self.delay, packet));
+        }
+        self.flush();
+    }
+
+    pub(crate) fn receive_inputs(&mut self) -> Vec<(u32, i8)> {
+        self.flush();
+
+        let mut received = Vec::new();
+        let mut buffer = [0; 512];
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((len, from)) if from == self.peer => {
+                    self.peer_ready = true;
+                    received.extend(decode_packet(&buffer[..len]));
+                }
+                Ok(_) => {}
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                // Windows reports ICMP port unreachable as a receive error; the peer may
+                // simply not be running yet.
+                Err(error) if error.kind() == io::ErrorKind::ConnectionReset => {}
+                Err(error) => {
+                    warn!("failed to receive packet: {error}");
+                    break;
+                }
+            }
+        }
+        received
+    }
+
+    fn flush(&mut self) {
+        let now = Instant::now();
+        while let Some((send_at, _)) = self.outgoing.front() {
+            if *send_at > now {
+                break;
+            }
+            let (_, packet) = self.outgoing.pop_front().unwrap();
+            match self.socket.send_to(&packet, self.peer) {
+                Err(error) if error.kind() != io::ErrorKind::WouldBlock => {
+                    warn!("failed to send packet: {error}");
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+pub fn net_plugin(app: &mut App) {
+    if let Some(config) = NetConfig::from_env() {
+        app.insert_resource(config);
+    }
+    app.add_systems(OnEnter(GlobalGameState::Game), start_session.run_if(online))
+        .add_systems(OnExit(GlobalGameState::Game), end_session);
+}
+
+fn online(settings: Res<GameSettings>, config: Option<Res<NetConfig>>) -> bool {
+    settings.online && config.is_some()
+}
+
+fn start_session(
+    mut commands: Commands,
+    config: Res<NetConfig>,
+    mut game_state: ResMut<NextState<GlobalGameState>>,
+) {
+    match NetSession::bind(&config) {
+        Ok(session) => commands.insert_resource(session),
+        Err(error) => {
+            error!("failed to bind {}: {error}", config.bind);
+            game_state.set(GlobalGameState::Menu);
+        }
+    }
+}
+
+fn end_session(mut commands: Commands) {
+    commands.remove_resource::<NetSession>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packets_round_trip() {
+        let inputs = [0, 1, -1, i8::MAX, i8::MIN, 42];
+        let packet = encode_packet(1000, &inputs);
+        assert_eq!(packet.len(), PACKET_HEADER + inputs.len());
+
+        let mut decoded = decode_packet(&packet);
+        decoded.reverse();
+        let expected: Vec<_> = (995..=1000).zip(inputs).collect();
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn packets_only_carry_recent_inputs() {
+        let inputs: Vec<i8> = (0..100).collect();
+        let decoded = decode_packet(&encode_packet(99, &inputs));
+        assert_eq!(decoded.len(), INPUT_REDUNDANCY);
+        assert_eq!(decoded.first(), Some(&(99, 99)));
+        assert_eq!(decoded.last(), Some(&(36, 36)));
+    }
+
+    #[test]
+    fn inputs_before_frame_zero_are_dropped() {
+        let decoded = decode_packet(&encode_packet(1, &[5, 6, 7]));
+        assert_eq!(decoded, [(1, 7), (0, 6)]);
+    }
+
+    #[test]
+    fn packets_shorter_than_the_header_are_ignored() {
+        assert!(decode_packet(&[1, 0, 0]).is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use super::loading::Preload;
+use super::theme::{Theme, ThemeColor};
 use super::GlobalGameState;
 use bevy::prelude::*;
 
@@ -9,12 +11,18 @@ pub fn splash_plugin(app: &mut App) {
         .add_systems(Update, countdown.run_if(in_state(GlobalGameState::Splash)));
 }
 
-fn splash_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn splash_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    mut preload: ResMut<Preload>,
+) {
     let bevy_logo = asset_server.load("bevy_logo_bevy.png");
+    preload.add(bevy_logo.clone());
 
     commands.spawn((
         DespawnOnExit(GlobalGameState::Splash),
-        BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+        theme.background_color(ThemeColor::MenuBackground),
         Node {
             align_items: AlignItems::Center,
             justify_content: JustifyContent::Center,
@@ -31,8 +39,24 @@ fn countdown(
     mut game_state: ResMut<NextState<GlobalGameState>>,
     time: Res<Time>,
     mut timer: ResMut<SplashTimer>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
 ) {
-    if timer.tick(time.delta()).is_finished() {
-        game_state.set(GlobalGameState::Menu);
+    let skipped = any_button_pressed(&keyboard_input, &mouse_input, &gamepads);
+    if timer.tick(time.delta()).is_finished() || skipped {
+        game_state.set(GlobalGameState::Loading);
     }
 }
+
+pub(crate) fn any_button_pressed(
+    keyboard_input: &ButtonInput<KeyCode>,
+    mouse_input: &ButtonInput<MouseButton>,
+    gamepads: &Query<&Gamepad>,
+) -> bool {
+    keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some())
+}
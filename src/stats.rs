@@ -0,0 +1,161 @@
+use super::save_dir;
+use bevy::prelude::*;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// Lifetime totals over all offline games, stored as `key = value` lines.
+#[derive(Resource, Default)]
+pub(crate) struct Stats {
+    pub bricks_broken: u64,
+    pub balls_lost: u64,
+    pub paddle_hits: u64,
+    pub longest_rally: u64,
+    pub fastest_clear: Option<f32>,
+    pub most_endless_rows: u64,
+    pub play_time: f64,
+    pub games_played: u64,
+}
+
+impl Stats {
+    fn path() -> PathBuf {
+        save_dir().join("stats.txt")
+    }
+
+    fn load() -> io::Result<Self> {
+        match fs::read_to_string(Self::path()) {
+            Ok(source) => source.parse(),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub(crate) fn save(&self) -> io::Result<()> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+    }
+}
+
+impl FromStr for Stats {
+    type Err = io::Error;
+
+    fn from_str(source: &str) -> io::Result<Self> {
+        let invalid = |line: usize| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {line}: invalid statistic"),
+            )
+        };
+
+        let mut stats = Self::default();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| invalid(index + 1))?;
+            let value = value.trim();
+            let parsed = match key.trim() {
+                "bricks-broken" => value.parse().map(|value| stats.bricks_broken = value).ok(),
+                "balls-lost" => value.parse().map(|value| stats.balls_lost = value).ok(),
+                "paddle-hits" => value.parse().map(|value| stats.paddle_hits = value).ok(),
+                "longest-rally" => value.parse().map(|value| stats.longest_rally = value).ok(),
+                "fastest-clear" => value
+                    .parse()
+                    .map(|value| stats.fastest_clear = Some(value))
+                    .ok(),
+                "most-endless-rows" => value
+                    .parse()
+                    .map(|value| stats.most_endless_rows = value)
+                    .ok(),
+                "play-time" => value.parse().map(|value| stats.play_time = value).ok(),
+                "games-played" => value.parse().map(|value| stats.games_played = value).ok(),
+                // Keys from newer versions are skipped rather than rejected.
+                _ => Some(()),
+            };
+            parsed.ok_or_else(|| invalid(index + 1))?;
+        }
+        Ok(stats)
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "bricks-broken = {}", self.bricks_broken)?;
+        writeln!(f, "balls-lost = {}", self.balls_lost)?;
+        writeln!(f, "paddle-hits = {}", self.paddle_hits)?;
+        writeln!(f, "longest-rally = {}", self.longest_rally)?;
+        if let Some(fastest_clear) = self.fastest_clear {
+            writeln!(f, "fastest-clear = {fastest_clear}")?;
+        }
+        writeln!(f, "most-endless-rows = {}", self.most_endless_rows)?;
+        writeln!(f, "play-time = {}", self.play_time)?;
+        writeln!(f, "games-played = {}", self.games_played)
+    }
+}
+
+pub(crate) fn format_duration(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+pub fn stats_plugin(app: &mut App) {
+    let stats = Stats::load().unwrap_or_else(|error| {
+        warn!("failed to read statistics: {error}");
+        Stats::default()
+    });
+    app.insert_resource(stats);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_text() {
+        let stats = Stats {
+            bricks_broken: 1234,
+            balls_lost: 56,
+            paddle_hits: 789,
+            longest_rally: 42,
+            fastest_clear: Some(61.25),
+            most_endless_rows: 17,
+            play_time: 3725.5,
+            games_played: 9,
+        };
+        let source = stats.to_string();
+        let parsed: Stats = source.parse().unwrap();
+        assert_eq!(parsed.to_string(), source);
+        assert_eq!(parsed.fastest_clear, Some(61.25));
+        assert_eq!(parsed.play_time, 3725.5);
+
+        let never_cleared: Stats = Stats::default().to_string().parse().unwrap();
+        assert_eq!(never_cleared.fastest_clear, None);
+    }
+
+    #[test]
+    fn skips_unknown_keys_and_rejects_bad_values() {
+        let stats: Stats = "bricks-broken = 3\npowerups-caught = 8\n\ngames-played = 2"
+            .parse()
+            .unwrap();
+        assert_eq!(stats.bricks_broken, 3);
+        assert_eq!(stats.games_played, 2);
+
+        let error = "bricks-broken = 3\nballs-lost = many"
+            .parse::<Stats>()
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "line 2: invalid statistic");
+        assert!("bricks-broken 3".parse::<Stats>().is_err());
+    }
+}
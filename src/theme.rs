@@ -0,0 +1,352 @@
+use bevy::prelude::*;
+
+pub(crate) const BRICK_COLOR_COUNT: usize = 10;
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub(crate) enum ThemeKind {
+    #[default]
+    Light,
+    Dark,
+    Retro,
+    Neon,
+}
+
+impl ThemeKind {
+    const ALL: [ThemeKind; 4] = [
+        ThemeKind::Light,
+        ThemeKind::Dark,
+        ThemeKind::Retro,
+        ThemeKind::Neon,
+    ];
+
+    pub(crate) fn message_id(self) -> &'static str {
+        match self {
+            ThemeKind::Light => "theme-light",
+            ThemeKind::Dark => "theme-dark",
+            ThemeKind::Retro => "theme-retro",
+            ThemeKind::Neon => "theme-neon",
+        }
+    }
+
+    pub(crate) fn cycle(self, step: isize) -> Self {
+        crate::cycle(&Self::ALL, self, step)
+    }
+}
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub(crate) enum Palette {
+    #[default]
+    Standard,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+    HighContrast,
+}
+
+impl Palette {
+    const ALL: [Palette; 5] = [
+        Palette::Standard,
+        Palette::Deuteranopia,
+        Palette::Protanopia,
+        Palette::Tritanopia,
+        Palette::HighContrast,
+    ];
+
+    pub(crate) fn message_id(self) -> &'static str {
+        match self {
+            Palette::Standard => "palette-standard",
+            Palette::Deuteranopia => "palette-deuteranopia",
+            Palette::Protanopia => "palette-protanopia",
+            Palette::Tritanopia => "palette-tritanopia",
+            Palette::HighContrast => "palette-high-contrast",
+        }
+    }
+
+    pub(crate) fn cycle(self, step: isize) -> Self {
+        crate::cycle(&Self::ALL, self, step)
+    }
+
+    // Deuteranopia uses the Okabe-Ito set and protanopia Paul Tol's muted set, both padded
+    // with extra hues. Tritanopia keeps to the red/teal axis and varies lightness instead.
+    fn bricks(self) -> Option<[Color; BRICK_COLOR_COUNT]> {
+        let colors = match self {
+            Palette::Standard => return None,
+            Palette::Deuteranopia => [
+                (230, 159, 0),
+                (86, 180, 233),
+                (0, 158, 115),
+                (240, 228, 66),
+                (0, 114, 178),
+                (213, 94, 0),
+                (204, 121, 167),
+                (136, 34, 85),
+                (51, 34, 136),
+                (153, 153, 51),
+            ],
+            Palette::Protanopia => [
+                (204, 102, 119),
+                (51, 34, 136),
+                (221, 204, 119),
+                (17, 119, 51),
+                (136, 204, 238),
+                (136, 34, 85),
+                (68, 170, 153),
+                (153, 153, 51),
+                (170, 68, 153),
+                (102, 153, 204),
+            ],
+            Palette::Tritanopia => [
+                (228, 26, 28),
+                (255, 127, 127),
+                (153, 0, 0),
+                (0, 150, 160),
+                (0, 90, 100),
+                (120, 210, 220),
+                (255, 105, 180),
+                (200, 80, 120),
+                (60, 60, 60),
+                (0, 180, 130),
+            ],
+            Palette::HighContrast => [
+                (255, 255, 0),
+                (0, 255, 255),
+                (255, 0, 255),
+                (255, 255, 255),
+                (0, 255, 0),
+                (255, 128, 0),
+                (255, 64, 64),
+                (128, 192, 255),
+                (255, 192, 224),
+                (192, 255, 128),
+            ],
+        };
+        Some(colors.map(|(r, g, b)| Color::srgb_u8(r, g, b)))
+    }
+}
+
+// Which theme colour a spawned UI node uses, so it can be recoloured when the theme changes.
+#[derive(Component, Clone, Copy)]
+pub(crate) enum ThemeColor {
+    Text,
+    MutedText,
+    HudText,
+    MenuBackground,
+    Overlay,
+    Button,
+}
+
+#[derive(Resource, Clone)]
+pub(crate) struct Theme {
+    pub kind: ThemeKind,
+    pub palette: Palette,
+    pub background: Color,
+    pub menu_background: Color,
+    pub overlay: Color,
+    pub text: Color,
+    pub muted_text: Color,
+    pub hud_text: Color,
+    pub button: Color,
+    pub button_hovered: Color,
+    pub button_pressed: Color,
+    pub paddles: [Color; 2],
+    pub ball: Color,
+    pub divider: Color,
+    pub bricks: [Color; BRICK_COLOR_COUNT],
+    pub unbreakable: Color,
+    pub empty_cell: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new(ThemeKind::default(), Palette::default())
+    }
+}
+
+impl Theme {
+    pub(crate) fn new(kind: ThemeKind, palette: Palette) -> Self {
+        let mut theme = match kind {
+            ThemeKind::Light => Self {
+                kind,
+                palette,
+                background: Color::srgb(0.95, 0.95, 0.95),
+                menu_background: Color::srgb(0.15, 0.15, 0.15),
+                overlay: Color::srgba(0.0, 0.0, 0.0, 0.5),
+                text: Color::WHITE,
+                muted_text: Color::srgb(0.8, 0.8, 0.8),
+                hud_text: Color::srgb(0.2, 0.2, 0.2),
+                button: Color::srgb(0.3, 0.3, 0.3),
+                button_hovered: Color::srgb(0.5, 0.5, 0.5),
+                button_pressed: Color::srgb(0.4, 0.4, 0.4),
+                paddles: [Color::srgb(0.6, 0.2, 0.2), Color::srgb(0.2, 0.3, 0.6)],
+                ball: Color::srgb(0.6, 0.1, 0.5),
+                divider: Color::srgb(0.3, 0.3, 0.3),
+                bricks: [
+                    Color::srgb(0.86, 0.24, 0.24),
+                    Color::srgb(0.93, 0.49, 0.19),
+                    Color::srgb(0.95, 0.77, 0.20),
+                    Color::srgb(0.55, 0.76, 0.29),
+                    Color::srgb(0.20, 0.63, 0.42),
+                    Color::srgb(0.18, 0.65, 0.72),
+                    Color::srgb(0.24, 0.45, 0.80),
+                    Color::srgb(0.43, 0.33, 0.78),
+                    Color::srgb(0.70, 0.31, 0.70),
+                    Color::srgb(0.85, 0.36, 0.56),
+                ],
+                unbreakable: Color::srgb(0.45, 0.45, 0.45),
+                empty_cell: Color::srgb(0.88, 0.88, 0.88),
+            },
+            ThemeKind::Dark => Self {
+                kind,
+                palette,
+                background: Color::srgb(0.1, 0.1, 0.12),
+                menu_background: Color::srgb(0.06, 0.06, 0.08),
+                overlay: Color::srgba(0.0, 0.0, 0.0, 0.6),
+                text: Color::srgb(0.92, 0.92, 0.92),
+                muted_text: Color::srgb(0.65, 0.65, 0.7),
+                hud_text: Color::srgb(0.8, 0.8, 0.85),
+                button: Color::srgb(0.2, 0.2, 0.24),
+                button_hovered: Color::srgb(0.35, 0.35, 0.4),
+                button_pressed: Color::srgb(0.28, 0.28, 0.32),
+                paddles: [Color::srgb(0.85, 0.4, 0.4), Color::srgb(0.4, 0.55, 0.9)],
+                ball: Color::srgb(0.95, 0.95, 0.95),
+                divider: Color::srgb(0.3, 0.3, 0.35),
+                bricks: [
+                    Color::srgb(0.75, 0.29, 0.29),
+                    Color::srgb(0.80, 0.48, 0.25),
+                    Color::srgb(0.82, 0.70, 0.28),
+                    Color::srgb(0.50, 0.68, 0.32),
+                    Color::srgb(0.25, 0.58, 0.42),
+                    Color::srgb(0.24, 0.58, 0.65),
+                    Color::srgb(0.30, 0.44, 0.72),
+                    Color::srgb(0.44, 0.36, 0.70),
+                    Color::srgb(0.63, 0.35, 0.63),
+                    Color::srgb(0.75, 0.38, 0.54),
+                ],
+                unbreakable: Color::srgb(0.4, 0.4, 0.45),
+                empty_cell: Color::srgb(0.16, 0.16, 0.19),
+            },
+            ThemeKind::Retro => Self {
+                kind,
+                palette,
+                background: Color::srgb(0.0, 0.0, 0.0),
+                menu_background: Color::srgb(0.02, 0.05, 0.02),
+                overlay: Color::srgba(0.0, 0.0, 0.0, 0.7),
+                text: Color::srgb(0.35, 1.0, 0.35),
+                muted_text: Color::srgb(0.2, 0.7, 0.2),
+                hud_text: Color::srgb(0.35, 1.0, 0.35),
+                button: Color::srgb(0.05, 0.2, 0.05),
+                button_hovered: Color::srgb(0.1, 0.4, 0.1),
+                button_pressed: Color::srgb(0.08, 0.3, 0.08),
+                paddles: [Color::srgb(0.78, 0.28, 0.28), Color::srgb(0.26, 0.36, 0.78)],
+                ball: Color::srgb(0.78, 0.78, 0.78),
+                divider: Color::srgb(0.56, 0.56, 0.56),
+                bricks: [
+                    Color::srgb(0.78, 0.28, 0.28),
+                    Color::srgb(0.78, 0.42, 0.23),
+                    Color::srgb(0.71, 0.48, 0.19),
+                    Color::srgb(0.64, 0.64, 0.16),
+                    Color::srgb(0.28, 0.63, 0.28),
+                    Color::srgb(0.26, 0.62, 0.62),
+                    Color::srgb(0.26, 0.28, 0.78),
+                    Color::srgb(0.47, 0.26, 0.71),
+                    Color::srgb(0.63, 0.26, 0.63),
+                    Color::srgb(0.56, 0.56, 0.56),
+                ],
+                unbreakable: Color::srgb(0.33, 0.33, 0.33),
+                empty_cell: Color::srgb(0.08, 0.08, 0.08),
+            },
+            ThemeKind::Neon => Self {
+                kind,
+                palette,
+                background: Color::srgb(0.03, 0.0, 0.08),
+                menu_background: Color::srgb(0.06, 0.0, 0.12),
+                overlay: Color::srgba(0.03, 0.0, 0.08, 0.7),
+                text: Color::srgb(0.0, 1.0, 0.95),
+                muted_text: Color::srgb(1.0, 0.3, 0.85),
+                hud_text: Color::srgb(0.0, 1.0, 0.95),
+                button: Color::srgb(0.2, 0.0, 0.3),
+                button_hovered: Color::srgb(0.45, 0.0, 0.6),
+                button_pressed: Color::srgb(0.32, 0.0, 0.45),
+                paddles: [Color::srgb(1.0, 0.2, 0.6), Color::srgb(0.2, 0.9, 1.0)],
+                ball: Color::srgb(1.0, 1.0, 0.3),
+                divider: Color::srgb(0.6, 0.0, 1.0),
+                bricks: [
+                    Color::srgb(1.0, 0.1, 0.4),
+                    Color::srgb(1.0, 0.5, 0.0),
+                    Color::srgb(1.0, 0.95, 0.1),
+                    Color::srgb(0.5, 1.0, 0.0),
+                    Color::srgb(0.0, 1.0, 0.5),
+                    Color::srgb(0.0, 1.0, 1.0),
+                    Color::srgb(0.2, 0.5, 1.0),
+                    Color::srgb(0.6, 0.2, 1.0),
+                    Color::srgb(1.0, 0.2, 1.0),
+                    Color::srgb(1.0, 0.4, 0.7),
+                ],
+                unbreakable: Color::srgb(0.5, 0.5, 0.6),
+                empty_cell: Color::srgb(0.1, 0.04, 0.18),
+            },
+        };
+
+        if let Some(bricks) = palette.bricks() {
+            theme.bricks = bricks;
+        }
+        if palette == Palette::HighContrast {
+            theme.background = Color::BLACK;
+            theme.menu_background = Color::BLACK;
+            theme.overlay = Color::srgba(0.0, 0.0, 0.0, 0.85);
+            theme.text = Color::WHITE;
+            theme.muted_text = Color::WHITE;
+            theme.hud_text = Color::WHITE;
+            theme.paddles = [Color::WHITE, Color::srgb(1.0, 1.0, 0.0)];
+            theme.ball = Color::WHITE;
+            theme.divider = Color::WHITE;
+            theme.unbreakable = Color::srgb(0.5, 0.5, 0.5);
+            theme.empty_cell = Color::srgb(0.15, 0.15, 0.15);
+        }
+        theme
+    }
+
+    pub(crate) fn color(&self, role: ThemeColor) -> Color {
+        match role {
+            ThemeColor::Text => self.text,
+            ThemeColor::MutedText => self.muted_text,
+            ThemeColor::HudText => self.hud_text,
+            ThemeColor::MenuBackground => self.menu_background,
+            ThemeColor::Overlay => self.overlay,
+            ThemeColor::Button => self.button,
+        }
+    }
+
+    pub(crate) fn text_color(&self, role: ThemeColor) -> (TextColor, ThemeColor) {
+        (TextColor(self.color(role)), role)
+    }
+
+    pub(crate) fn background_color(&self, role: ThemeColor) -> (BackgroundColor, ThemeColor) {
+        (BackgroundColor(self.color(role)), role)
+    }
+}
+
+pub fn theme_plugin(app: &mut App) {
+    let theme = Theme::default();
+    app.insert_resource(ClearColor(theme.background))
+        .insert_resource(theme)
+        .add_systems(PostUpdate, apply_theme);
+}
+
+fn apply_theme(
+    theme: Res<Theme>,
+    mut clear_color: ResMut<ClearColor>,
+    mut text_query: Query<(&ThemeColor, &mut TextColor)>,
+    mut background_query: Query<(&ThemeColor, &mut BackgroundColor)>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+    clear_color.0 = theme.background;
+    for (role, mut color) in &mut text_query {
+        color.0 = theme.color(*role);
+    }
+    for (role, mut color) in &mut background_query {
+        color.0 = theme.color(*role);
+    }
+}